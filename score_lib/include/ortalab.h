@@ -0,0 +1,214 @@
+#ifndef ORTALAB_H
+#define ORTALAB_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Joker slots of a run without vouchers or Negative jokers
+ */
+#define DEFAULT_JOKER_SLOTS 5
+
+/**
+ * Most outcomes `Distribution::exact` enumerates before giving up
+ */
+#define MAX_OUTCOMES 4096
+
+/**
+ * Status codes returned by the `ortalab_round_*` functions
+ */
+typedef enum OrtalabStatus {
+  OrtalabStatus_Ok = 0,
+  OrtalabStatus_NullPointer = 1,
+  OrtalabStatus_InvalidUtf8 = 2,
+  OrtalabStatus_ParseError = 3,
+  /**
+   * The round parsed, but cannot be played, e.g. more jokers than joker slots
+   */
+  OrtalabStatus_InvalidRound = 4,
+} OrtalabStatus;
+
+/**
+ * A round being assembled from C
+ */
+typedef struct OrtalabRound OrtalabRound;
+
+/**
+ * A scored round; trace entries live as long as the handle
+ */
+typedef struct OrtalabScore OrtalabScore;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Create an empty round
+ */
+struct OrtalabRound *ortalab_round_new(void);
+
+/**
+ * Create a round from a JSON round, with every key a YAML round file has
+ * (`cards_played`, `blind`, `context`, `joker_slots`, ...)
+ *
+ * On success `*out` is the new round, to be released with `ortalab_round_free`.
+ * Returns `ParseError` for malformed JSON or unknown keys.
+ *
+ * # Safety
+ * `json` must be a NUL-terminated string, `out` must be valid for writes.
+ */
+enum OrtalabStatus ortalab_round_from_json(const char *json, struct OrtalabRound **out);
+
+/**
+ * Add a played card, e.g. `"10♠ Glass Foil"` or `"K♥ Stone Red Seal"`
+ *
+ * # Safety
+ * `round` must come from `ortalab_round_new` or `ortalab_round_from_json`, `card` must be a
+ * NUL-terminated string.
+ */
+enum OrtalabStatus ortalab_round_add_played(struct OrtalabRound *round, const char *card);
+
+/**
+ * Add a card held in hand
+ *
+ * # Safety
+ * `round` must come from `ortalab_round_new` or `ortalab_round_from_json`, `card` must be a
+ * NUL-terminated string.
+ */
+enum OrtalabStatus ortalab_round_add_held(struct OrtalabRound *round, const char *card);
+
+/**
+ * Add a joker, e.g. `"Baron Polychrome"`
+ *
+ * # Safety
+ * `round` must come from `ortalab_round_new` or `ortalab_round_from_json`, `joker` must be a
+ * NUL-terminated string.
+ */
+enum OrtalabStatus ortalab_round_add_joker(struct OrtalabRound *round, const char *joker);
+
+/**
+ * Set the money at the start of the round, `0` by default
+ *
+ * # Safety
+ * `round` must come from `ortalab_round_new` or `ortalab_round_from_json`.
+ */
+enum OrtalabStatus ortalab_round_set_money(struct OrtalabRound *round, int64_t money);
+
+/**
+ * Score with exact rational arithmetic instead of floating point, off by default
+ *
+ * # Safety
+ * `round` must come from `ortalab_round_new` or `ortalab_round_from_json`.
+ */
+enum OrtalabStatus ortalab_round_set_exact(struct OrtalabRound *round, bool exact);
+
+/**
+ * Check the round can actually be played: `InvalidRound` if not, e.g. with more
+ * jokers than joker slots
+ *
+ * # Safety
+ * `round` must come from `ortalab_round_new` or `ortalab_round_from_json`.
+ */
+enum OrtalabStatus ortalab_round_validate(const struct OrtalabRound *round);
+
+/**
+ * Score the round; the round is left untouched and can be scored again
+ *
+ * # Safety
+ * `round` must come from `ortalab_round_new` or `ortalab_round_from_json`. Returns
+ * NULL if `round` is NULL or fails `ortalab_round_validate`.
+ */
+struct OrtalabScore *ortalab_round_score(const struct OrtalabRound *round);
+
+/**
+ * Release a round
+ *
+ * # Safety
+ * `round` must come from `ortalab_round_new` or `ortalab_round_from_json` and not be used
+ * afterwards.
+ */
+void ortalab_round_free(struct OrtalabRound *round);
+
+/**
+ * Final score, chips times mult rounded down
+ *
+ * # Safety
+ * `score` must come from `ortalab_round_score`.
+ */
+double ortalab_score_total(const struct OrtalabScore *score);
+
+/**
+ * Final score written out in full, every digit right after `ortalab_round_set_exact`;
+ * owned by `score`, NULL if `score` is NULL
+ *
+ * # Safety
+ * `score` must come from `ortalab_round_score`.
+ */
+const char *ortalab_score_exact_total(const struct OrtalabScore *score);
+
+/**
+ * Why the blind did not allow the hand, which then scored nothing; NULL when it was
+ * allowed, owned by `score`
+ *
+ * # Safety
+ * `score` must come from `ortalab_round_score`.
+ */
+const char *ortalab_score_rejection(const struct OrtalabScore *score);
+
+/**
+ * Chips after scoring
+ *
+ * # Safety
+ * `score` must come from `ortalab_round_score`.
+ */
+double ortalab_score_chips(const struct OrtalabScore *score);
+
+/**
+ * Mult after scoring
+ *
+ * # Safety
+ * `score` must come from `ortalab_round_score`.
+ */
+double ortalab_score_mult(const struct OrtalabScore *score);
+
+/**
+ * Money earned during the round, negative when money was spent
+ *
+ * # Safety
+ * `score` must come from `ortalab_round_score`.
+ */
+int64_t ortalab_score_money_delta(const struct OrtalabScore *score);
+
+/**
+ * Number of trace entries
+ *
+ * # Safety
+ * `score` must come from `ortalab_round_score`.
+ */
+uintptr_t ortalab_score_trace_len(const struct OrtalabScore *score);
+
+/**
+ * Trace entry `index`, or NULL when out of range; owned by `score`
+ *
+ * # Safety
+ * `score` must come from `ortalab_round_score`.
+ */
+const char *ortalab_score_trace_entry(const struct OrtalabScore *score, uintptr_t index);
+
+/**
+ * Release a score and its trace entries
+ *
+ * # Safety
+ * `score` must come from `ortalab_round_score` and not be used afterwards.
+ */
+void ortalab_score_free(struct OrtalabScore *score);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ORTALAB_H */
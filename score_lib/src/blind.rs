@@ -0,0 +1,211 @@
+use ortalib::{ PokerHand, Suit };
+use serde::{ Deserialize, Serialize };
+use std::fmt;
+
+use crate::{ card::{ CardUtils, PlayingCard }, HandHistory };
+
+/// Boss blinds that change how a hand scores
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Blind {
+    /// Base chips and mult of the poker hand are halved
+    #[serde(rename = "The Flint")]
+    TheFlint,
+    /// All Club cards are debuffed
+    #[serde(rename = "The Club")]
+    TheClub,
+    /// All Spade cards are debuffed
+    #[serde(rename = "The Goad")]
+    TheGoad,
+    /// All Diamond cards are debuffed
+    #[serde(rename = "The Window")]
+    TheWindow,
+    /// All Heart cards are debuffed
+    #[serde(rename = "The Head")]
+    TheHead,
+    /// All face cards are debuffed
+    #[serde(rename = "The Plant")]
+    ThePlant,
+    /// Must play 5 cards
+    #[serde(rename = "The Psychic")]
+    ThePsychic,
+    /// No repeat hand types this round
+    #[serde(rename = "The Eye")]
+    TheEye,
+    /// Play only one hand type this round
+    #[serde(rename = "The Mouth")]
+    TheMouth,
+}
+
+/// Why a blind does not allow the hand to be played; the hand scores nothing
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum IllegalPlay {
+    /// The Psychic
+    TooFewCards {
+        required: usize,
+        played: usize,
+    },
+    /// The Eye
+    HandAlreadyPlayed {
+        #[serde(serialize_with = "serialize_hand")]
+        hand: PokerHand,
+    },
+    /// The Mouth
+    WrongHand {
+        #[serde(serialize_with = "serialize_hand")]
+        allowed: PokerHand,
+        #[serde(serialize_with = "serialize_hand")]
+        played: PokerHand,
+    },
+}
+
+fn serialize_hand<S: serde::Serializer>(hand: &PokerHand, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(hand)
+}
+
+impl fmt::Display for IllegalPlay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IllegalPlay::TooFewCards { required, played } =>
+                write!(f, "must play {required} cards, played {played}"),
+            IllegalPlay::HandAlreadyPlayed { hand } =>
+                write!(f, "{hand} was already played this round"),
+            IllegalPlay::WrongHand { allowed, played } =>
+                write!(f, "only {allowed} may be played this round, not {played}"),
+        }
+    }
+}
+
+impl std::error::Error for IllegalPlay {}
+
+pub trait BlindUtils {
+    /// Whether the blind debuffs `card`
+    fn debuffs(&self, card: &PlayingCard) -> bool;
+    /// Base chips and mult of the poker hand under this blind
+    fn hand_value(&self, chips: f64, mult: f64) -> (f64, f64);
+    /// Reject the play if the blind forbids it; `history` excludes this hand
+    fn check_play(
+        &self,
+        cards_played: usize,
+        hand: PokerHand,
+        history: &HandHistory
+    ) -> Result<(), IllegalPlay>;
+}
+
+impl BlindUtils for Blind {
+    fn debuffs(&self, card: &PlayingCard) -> bool {
+        // Wild cards count as every suit, Stone cards as none
+        let suited = |suit: Suit| !card.is_stone() && (card.suit == suit || card.is_wild());
+        match self {
+            Blind::TheClub => suited(Suit::Clubs),
+            Blind::TheGoad => suited(Suit::Spades),
+            Blind::TheWindow => suited(Suit::Diamonds),
+            Blind::TheHead => suited(Suit::Hearts),
+            Blind::ThePlant => !card.is_stone() && card.rank.is_face(),
+            _ => false,
+        }
+    }
+
+    fn hand_value(&self, chips: f64, mult: f64) -> (f64, f64) {
+        match self {
+            // rounded half up, and mult never drops below 1
+            Blind::TheFlint => ((chips / 2.0 + 0.5).floor(), (mult / 2.0 + 0.5).floor().max(1.0)),
+            _ => (chips, mult),
+        }
+    }
+
+    fn check_play(
+        &self,
+        cards_played: usize,
+        hand: PokerHand,
+        history: &HandHistory
+    ) -> Result<(), IllegalPlay> {
+        match self {
+            Blind::ThePsychic if cards_played < 5 => {
+                Err(IllegalPlay::TooFewCards { required: 5, played: cards_played })
+            }
+            Blind::TheEye if history.round_plays(hand) > 0 => {
+                Err(IllegalPlay::HandAlreadyPlayed { hand })
+            }
+            Blind::TheMouth => {
+                // only one hand type can have been played against The Mouth
+                match history.round.iter().find(|(_, n)| **n > 0) {
+                    Some((&allowed, _)) if allowed != hand =>
+                        Err(IllegalPlay::WrongHand { allowed, played: hand }),
+                    _ => Ok(()),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ RoundInput, Score };
+
+    fn card(card: &str) -> PlayingCard {
+        card.parse().unwrap()
+    }
+
+    /// Scores `played` against `blind`, with `round_plays` as this round's history
+    fn scored(played: &[&str], blind: Blind, round_plays: &[(PokerHand, u32)]) -> Score {
+        let round = RoundInput {
+            cards_played: played.iter().map(|c| card(c)).collect(),
+            blind: Some(blind),
+            hand_history: HandHistory {
+                round: round_plays.iter().copied().collect(),
+                ..HandHistory::default()
+            },
+            ..RoundInput::default()
+        };
+        let mut score = Score::new(round);
+        score.calculate_score();
+        score
+    }
+
+    #[test]
+    fn the_flint_halves_rounding_up() {
+        // High Card (5, 1), Pair (10, 2), Three of a Kind (30, 3), Straight (30, 4)
+        assert_eq!(Blind::TheFlint.hand_value(5.0, 1.0), (3.0, 1.0));
+        assert_eq!(Blind::TheFlint.hand_value(10.0, 2.0), (5.0, 1.0));
+        assert_eq!(Blind::TheFlint.hand_value(30.0, 3.0), (15.0, 2.0));
+        assert_eq!(Blind::TheFlint.hand_value(30.0, 4.0), (15.0, 2.0));
+        // mult never drops below 1
+        assert_eq!(Blind::TheFlint.hand_value(0.0, 0.0), (0.0, 1.0));
+        // High Card (3, 1) + A♠ 11
+        assert_eq!(scored(&["A♠"], Blind::TheFlint, &[]).total().to_f64(), 14.0);
+    }
+
+    #[test]
+    fn suit_blinds_debuff_wild_cards_but_not_stone_cards() {
+        assert!(Blind::TheClub.debuffs(&card("2♣")));
+        assert!(!Blind::TheClub.debuffs(&card("2♠")));
+        assert!(Blind::TheClub.debuffs(&card("2♠ Wild")));
+        assert!(!Blind::TheClub.debuffs(&card("2♣ Stone")));
+        assert!(Blind::TheHead.debuffs(&card("K♦ Wild")));
+        assert!(!Blind::ThePlant.debuffs(&card("K♦ Stone")));
+    }
+
+    #[test]
+    fn the_eye_rejects_hands_already_played_this_round() {
+        // this hand is not in the history yet, so it does not count against itself
+        assert_eq!(scored(&["K♥", "K♦"], Blind::TheEye, &[]).rejection(), None);
+        let score = scored(&["K♥", "K♦"], Blind::TheEye, &[(PokerHand::Pair, 1)]);
+        assert_eq!(score.rejection(), Some(IllegalPlay::HandAlreadyPlayed { hand: PokerHand::Pair }));
+        assert_eq!(score.total().to_f64(), 0.0);
+        let score = scored(&["K♥", "Q♦"], Blind::TheEye, &[(PokerHand::Pair, 1)]);
+        assert_eq!(score.rejection(), None);
+    }
+
+    #[test]
+    fn the_mouth_allows_only_the_first_hand_played() {
+        assert_eq!(scored(&["K♥", "K♦"], Blind::TheMouth, &[]).rejection(), None);
+        let score = scored(&["K♥", "K♦"], Blind::TheMouth, &[(PokerHand::Pair, 2)]);
+        assert_eq!(score.rejection(), None);
+        let score = scored(&["K♥", "Q♦"], Blind::TheMouth, &[(PokerHand::Pair, 2)]);
+        let rejection = IllegalPlay::WrongHand { allowed: PokerHand::Pair, played: PokerHand::HighCard };
+        assert_eq!(score.rejection(), Some(rejection));
+    }
+}
@@ -1,13 +1,263 @@
-use ortalib::{ Card, Enhancement };
+use ortalib::{ Rank, Suit };
+use serde::{ de, Deserialize, Deserializer, Serialize, Serializer };
+use std::{ fmt, str::FromStr };
+
+/// Card enhancements, a superset of `ortalib::Enhancement`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Enhancement {
+    Bonus,
+    Mult,
+    Wild,
+    Glass,
+    Steel,
+    /// +50 chips, no rank or suit, always scores
+    Stone,
+    Gold,
+    Lucky,
+}
+
+/// Editions, a superset of `ortalib::Edition`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Edition {
+    Foil,
+    Holographic,
+    Polychrome,
+    /// No score effect; +1 joker slot on a joker, +1 hand size on a playing card
+    Negative,
+}
+
+/// Seals, which `ortalib::Card` skips
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Seal {
+    /// Retriggers the card
+    Red,
+    Gold,
+    Blue,
+    Purple,
+}
+
+/// A playing card as `score_lib` models it: an `ortalib::Card` plus the
+/// enhancements and seals ortalib does not know about
+///
+/// Parsed from the same text as `ortalib::Card`, with an optional trailing
+/// seal and debuff flag, e.g. `K♥ Gold Foil Red Seal Debuffed`. Cards compare by value; use `CardId`
+/// to tell two identical cards apart.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PlayingCard {
+    pub rank: Rank,
+    pub suit: Suit,
+    pub enhancement: Option<Enhancement>,
+    pub edition: Option<Edition>,
+    pub seal: Option<Seal>,
+    /// Still counts towards the poker hand, but gives nothing when scored or held
+    pub debuffed: bool,
+}
 
 pub trait CardUtils {
     fn is_wild(&self) -> bool;
+    fn is_stone(&self) -> bool;
+    /// How many times the card's abilities trigger before jokers like Mime
+    fn triggers(&self) -> usize;
 }
-impl CardUtils for Card {
+impl CardUtils for PlayingCard {
     fn is_wild(&self) -> bool {
         match self.enhancement {
             Some(e) => e == Enhancement::Wild,
             _ => false,
         }
     }
+    fn is_stone(&self) -> bool {
+        self.enhancement == Some(Enhancement::Stone)
+    }
+    fn triggers(&self) -> usize {
+        // a Red Seal retriggers the card once
+        if self.seal == Some(Seal::Red) { 2 } else { 1 }
+    }
+}
+
+impl From<ortalib::Enhancement> for Enhancement {
+    fn from(e: ortalib::Enhancement) -> Self {
+        match e {
+            ortalib::Enhancement::Bonus => Enhancement::Bonus,
+            ortalib::Enhancement::Mult => Enhancement::Mult,
+            ortalib::Enhancement::Wild => Enhancement::Wild,
+            ortalib::Enhancement::Glass => Enhancement::Glass,
+            ortalib::Enhancement::Steel => Enhancement::Steel,
+        }
+    }
+}
+
+impl From<ortalib::Edition> for Edition {
+    fn from(e: ortalib::Edition) -> Self {
+        match e {
+            ortalib::Edition::Foil => Edition::Foil,
+            ortalib::Edition::Holographic => Edition::Holographic,
+            ortalib::Edition::Polychrome => Edition::Polychrome,
+        }
+    }
+}
+
+impl From<ortalib::Card> for PlayingCard {
+    fn from(card: ortalib::Card) -> Self {
+        PlayingCard {
+            rank: card.rank,
+            suit: card.suit,
+            enhancement: card.enhancement.map(Enhancement::from),
+            edition: card.edition.map(Edition::from),
+            seal: None,
+            debuffed: false,
+        }
+    }
+}
+
+impl FromStr for Enhancement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Stone" => Ok(Enhancement::Stone),
+            "Gold" => Ok(Enhancement::Gold),
+            "Lucky" => Ok(Enhancement::Lucky),
+            _ => s.parse::<ortalib::Enhancement>().map(Enhancement::from),
+        }
+    }
+}
+
+impl FromStr for Edition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Negative" => Ok(Edition::Negative),
+            _ => s.parse::<ortalib::Edition>().map(Edition::from),
+        }
+    }
+}
+
+impl fmt::Display for Edition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl FromStr for Seal {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Red" => Ok(Seal::Red),
+            "Gold" => Ok(Seal::Gold),
+            "Blue" => Ok(Seal::Blue),
+            "Purple" => Ok(Seal::Purple),
+            _ => Err(format!("Invalid Seal: `{s}`")),
+        }
+    }
+}
+
+impl FromStr for PlayingCard {
+    type Err = String;
+
+    /// `<rank><suit> [enhancement] [edition] [<color> Seal] [Debuffed]`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts: Vec<&str> = s.split_ascii_whitespace().collect();
+
+        let debuffed = parts.last() == Some(&"Debuffed") && parts.len() >= 2;
+        if debuffed {
+            parts.pop();
+        }
+
+        let seal = if parts.last() == Some(&"Seal") && parts.len() >= 3 {
+            parts.pop();
+            let color = parts.pop().unwrap_or_default();
+            Some(color.parse().map_err(|err| format!("Card `{s}` has invalid seal: {err}"))?)
+        } else {
+            None
+        };
+
+        let rank_suit = parts.first().ok_or("Cannot parse empty string")?;
+        // Suit is always exactly 1 char
+        let mut reversed = rank_suit.chars().rev();
+        let suit_str = reversed
+            .next()
+            .ok_or_else(|| format!("Card `{s}` missing rank / suit"))?
+            .to_string();
+        let rank_str: String = reversed.rev().collect();
+        let rank = rank_str.parse().map_err(|err| format!("Card `{s}` has invalid rank: {err}"))?;
+        let suit = suit_str.parse().map_err(|err| format!("Card `{s}` has invalid suit: {err}"))?;
+
+        let mut card = PlayingCard { rank, suit, enhancement: None, edition: None, seal, debuffed };
+        for part in &parts[1..] {
+            if let Ok(edition) = part.parse::<Edition>() {
+                if card.edition.replace(edition).is_some() {
+                    return Err(format!("Card `{s}` has more than one edition"));
+                }
+            } else if let Ok(enhancement) = part.parse::<Enhancement>() {
+                if card.enhancement.replace(enhancement).is_some() {
+                    return Err(format!("Card `{s}` has more than one enhancement"));
+                }
+            } else {
+                return Err(format!("Card `{s}` has invalid enhancement / edition: {part}"));
+            }
+        }
+        Ok(card)
+    }
+}
+
+impl fmt::Display for PlayingCard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.rank, self.suit)?;
+        if let Some(enhancement) = self.enhancement {
+            write!(f, " {enhancement:?}")?;
+        }
+        if let Some(edition) = self.edition {
+            write!(f, " {edition}")?;
+        }
+        if let Some(seal) = self.seal {
+            write!(f, " {seal:?} Seal")?;
+        }
+        if self.debuffed {
+            write!(f, " Debuffed")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for PlayingCard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Serialize for PlayingCard {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PlayingCard {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+/// Stable handle for a card in the round: its position among the cards played
+/// or the cards held in hand
+///
+/// Two identical `K♥` cards get different ids, so effects and trace entries can
+/// be attributed to the exact card that caused them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(tag = "zone", content = "index", rename_all = "snake_case")]
+pub enum CardId {
+    Played(usize),
+    Held(usize),
+}
+
+impl fmt::Display for CardId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CardId::Played(i) => write!(f, "played[{i}]"),
+            CardId::Held(i) => write!(f, "held[{i}]"),
+        }
+    }
 }
@@ -0,0 +1,123 @@
+use ortalib::{ Rank, Suit };
+
+use crate::{ card::{ CardId, CardUtils }, explain, HeldTarget, JokerCard, Score };
+
+/// Which cards an on-scored or on-held joker reacts to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardPredicate {
+    /// Every card, Stone cards included
+    Any,
+    /// Cards of the suit, wild cards included
+    Suit(Suit),
+    Ranks(Vec<Rank>),
+    Face,
+    /// Only the first face card among the scored cards, Stone cards skipped
+    FirstFace,
+}
+
+impl CardPredicate {
+    /// Stone cards have no rank or suit, so they only match `Any`
+    pub fn matches(&self, s: &Score, id: CardId) -> bool {
+        let card = s.card(id);
+        if card.is_stone() {
+            return *self == CardPredicate::Any;
+        }
+        match self {
+            CardPredicate::Any => true,
+            CardPredicate::Suit(suit) => card.suit == *suit || card.is_wild(),
+            CardPredicate::Ranks(ranks) => ranks.contains(&card.rank),
+            CardPredicate::Face => card.rank.is_face(),
+            CardPredicate::FirstFace => {
+                let first_face = s
+                    .scored_cards()
+                    .iter()
+                    .copied()
+                    .find(|id| !s.card(*id).is_stone() && s.card(*id).rank.is_face());
+                first_face == Some(id)
+            }
+        }
+    }
+}
+
+/// What a joker does for each matching card
+#[derive(Debug, Clone, PartialEq)]
+pub enum CardEffect {
+    Chips(f64),
+    Mult(f64),
+    /// Mult of this many times the card's rank value, e.g. Raised Fist
+    RankMult(f64),
+    XMult(f64),
+    Money(i64),
+    /// `numerator in denominator` chance of the inner effect
+    Chance(u32, u32, Box<CardEffect>),
+    /// The card triggers once more, counted before the card scores
+    Retrigger,
+}
+
+impl CardEffect {
+    pub fn apply(&self, s: &mut Score, joker: &JokerCard, id: CardId) {
+        match self {
+            CardEffect::Chips(chips) => {
+                s.chips += *chips;
+                explain!(s, "🃏 {:?} +{} Chips", joker, chips);
+            }
+            CardEffect::Mult(mult) => {
+                s.mult += *mult;
+                explain!(s, "🃏 {:?} +{} Mult", joker, mult);
+            }
+            CardEffect::RankMult(times) => {
+                let value = times * s.card(id).rank.rank_value();
+                s.mult += value;
+                explain!(s, "🃏 {:?} +{} Mult", joker, value);
+            }
+            CardEffect::XMult(mult) => {
+                s.mult *= *mult;
+                explain!(s, "🃏 {:?} x{} Mult", joker, mult);
+            }
+            CardEffect::Money(dollars) => {
+                s.earn(*dollars);
+                explain!(s, "🃏 {:?} +${} (${})", joker, dollars, s.money());
+            }
+            CardEffect::Chance(numerator, denominator, effect) => {
+                let what = format!("{joker}");
+                let scoring = !matches!(**effect, CardEffect::Money(_));
+                if s.roll(*numerator, *denominator, &what, scoring) {
+                    effect.apply(s, joker, id);
+                }
+            }
+            CardEffect::Retrigger => (),
+        }
+    }
+}
+
+/// An on-scored joker: the effects it applies to every scored card matching the predicate
+#[derive(Debug, Clone, PartialEq)]
+pub struct OnScored {
+    pub predicate: CardPredicate,
+    pub effects: Vec<CardEffect>,
+}
+
+impl OnScored {
+    pub fn new(predicate: CardPredicate, effects: Vec<CardEffect>) -> Self {
+        OnScored { predicate, effects }
+    }
+
+    pub fn retriggers(&self) -> bool {
+        self.effects.contains(&CardEffect::Retrigger)
+    }
+}
+
+/// A held-in-hand joker: the effects it applies to every targeted held card
+/// matching the predicate, once per trigger of the card
+#[derive(Debug, Clone, PartialEq)]
+pub struct OnHeld {
+    pub target: HeldTarget,
+    pub predicate: CardPredicate,
+    pub effects: Vec<CardEffect>,
+}
+
+impl OnHeld {
+    pub fn new(target: HeldTarget, predicate: CardPredicate, effects: Vec<CardEffect>) -> Self {
+        OnHeld { target, predicate, effects }
+    }
+}
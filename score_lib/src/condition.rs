@@ -0,0 +1,93 @@
+use std::collections::HashSet;
+
+use ortalib::{ PokerHand, Suit };
+
+use crate::{ card::CardUtils, utils::num_of_poker_by_suits, PlayingCard, Score };
+
+/// How many of something a condition asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Count {
+    Exactly(usize),
+    AtMost(usize),
+    AtLeast(usize),
+}
+
+impl Count {
+    pub fn matches(&self, n: usize) -> bool {
+        match *self {
+            Count::Exactly(m) => n == m,
+            Count::AtMost(m) => n <= m,
+            Count::AtLeast(m) => n >= m,
+        }
+    }
+}
+
+/// What a joker needs from the round before its effect triggers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Condition {
+    Always,
+    /// The cards played contain the hand, e.g. a Full House contains a Pair
+    ContainsHand(PokerHand),
+    CardsPlayed(Count),
+    /// Every card held in hand is one of these suits, wild cards included
+    HeldOnlySuits(Vec<Suit>),
+    /// The scoring cards cover all four suits, wild cards filling gaps, Stone cards ignored
+    ScoredEverySuit,
+    /// Times the scored poker hand was played this round, this hand included
+    HandPlayedThisRound(Count),
+    /// The round input has a round context
+    HasContext,
+    HandsLeft(Count),
+    DiscardsLeft(Count),
+    /// The round input lists the full deck
+    HasFullDeck,
+}
+
+impl Condition {
+    /// The single evaluator behind every joker condition
+    pub fn evaluate(&self, s: &Score) -> bool {
+        match self {
+            Condition::Always => true,
+            Condition::ContainsHand(hand) => s.contains_hand(*hand),
+            Condition::CardsPlayed(count) => count.matches(s.cards_played.len()),
+            Condition::HeldOnlySuits(suits) => {
+                let n = num_of_poker_by_suits(&s.cards_held_in_hand, suits.clone());
+                s.cards_held_in_hand.len() == n
+            }
+            Condition::ScoredEverySuit => {
+                // Stone cards have no suit
+                let suited: Vec<&PlayingCard> = s
+                    .scored()
+                    .filter(|x| !x.is_stone())
+                    .collect();
+                let small: HashSet<Suit> = suited
+                    .iter()
+                    .filter(|x| !x.is_wild())
+                    .map(|x| x.suit)
+                    .collect();
+                let big: HashSet<Suit> = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades]
+                    .iter()
+                    .cloned()
+                    .collect();
+                let diff: HashSet<Suit> = big.difference(&small).cloned().collect();
+
+                let n_wild_cards = suited
+                    .iter()
+                    .filter(|x| x.is_wild())
+                    .count();
+                diff.len() <= n_wild_cards
+            }
+            Condition::HandPlayedThisRound(count) => {
+                count.matches(s.hand_history().round_plays(s.best_poker_hand()) as usize)
+            }
+            Condition::HasContext => s.context().is_some(),
+            Condition::HandsLeft(count) => {
+                s.context().is_some_and(|c| count.matches(c.hands_left as usize))
+            }
+            Condition::DiscardsLeft(count) => {
+                s.context().is_some_and(|c| count.matches(c.discards_left as usize))
+            }
+            Condition::HasFullDeck => s.deck().is_some(),
+        }
+    }
+}
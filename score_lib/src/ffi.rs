@@ -0,0 +1,393 @@
+//! C ABI for embedding the scorer without spawning `ortalab`.
+//!
+//! Rounds are built up card by card from the same strings the YAML input uses
+//! (`"K♥ Steel"`, `"Baron Polychrome"`), or read whole from a JSON round, then
+//! scored into an opaque handle; both handles must be released with their `_free`
+//! function. `include/ortalab.h` is
+//! generated from this file with `cbindgen --config cbindgen.toml`.
+use std::{ ffi::{ c_char, CStr, CString }, ptr, str::FromStr };
+
+use crate::{ Arithmetic, RoundInput, Score };
+
+/// A round being assembled from C
+pub struct OrtalabRound {
+    input: RoundInput,
+    arithmetic: Arithmetic,
+}
+
+/// A scored round; trace entries live as long as the handle
+pub struct OrtalabScore {
+    score: f64,
+    exact_score: CString,
+    rejection: Option<CString>,
+    chips: f64,
+    mult: f64,
+    money_delta: i64,
+    trace: Vec<CString>,
+}
+
+/// Status codes returned by the `ortalab_round_*` functions
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrtalabStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidUtf8 = 2,
+    ParseError = 3,
+    /// The round parsed, but cannot be played, e.g. more jokers than joker slots
+    InvalidRound = 4,
+}
+
+unsafe fn to_str<'a>(s: *const c_char) -> Result<&'a str, OrtalabStatus> {
+    if s.is_null() {
+        return Err(OrtalabStatus::NullPointer);
+    }
+    CStr::from_ptr(s).to_str().map_err(|_| OrtalabStatus::InvalidUtf8)
+}
+
+unsafe fn parse<T: FromStr>(s: *const c_char) -> Result<T, OrtalabStatus> {
+    to_str(s)?.parse().map_err(|_| OrtalabStatus::ParseError)
+}
+
+fn new_round(input: RoundInput) -> *mut OrtalabRound {
+    Box::into_raw(Box::new(OrtalabRound { input, arithmetic: Arithmetic::Float }))
+}
+
+unsafe fn push<T: FromStr>(
+    round: *mut OrtalabRound,
+    s: *const c_char,
+    target: fn(&mut RoundInput) -> &mut Vec<T>
+) -> OrtalabStatus {
+    let Some(round) = round.as_mut() else {
+        return OrtalabStatus::NullPointer;
+    };
+    match parse(s) {
+        Ok(value) => {
+            target(&mut round.input).push(value);
+            OrtalabStatus::Ok
+        }
+        Err(status) => status,
+    }
+}
+
+/// Create an empty round
+#[no_mangle]
+pub extern "C" fn ortalab_round_new() -> *mut OrtalabRound {
+    new_round(RoundInput::default())
+}
+
+/// Create a round from a JSON round, with every key a YAML round file has
+/// (`cards_played`, `blind`, `context`, `joker_slots`, ...)
+///
+/// On success `*out` is the new round, to be released with `ortalab_round_free`.
+/// Returns `ParseError` for malformed JSON or unknown keys.
+///
+/// # Safety
+/// `json` must be a NUL-terminated string, `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn ortalab_round_from_json(
+    json: *const c_char,
+    out: *mut *mut OrtalabRound
+) -> OrtalabStatus {
+    if out.is_null() {
+        return OrtalabStatus::NullPointer;
+    }
+    let json = match to_str(json) {
+        Ok(json) => json,
+        Err(status) => {
+            return status;
+        }
+    };
+    match serde_json::from_str(json) {
+        Ok(input) => {
+            *out = new_round(input);
+            OrtalabStatus::Ok
+        }
+        Err(_) => OrtalabStatus::ParseError,
+    }
+}
+
+/// Add a played card, e.g. `"10♠ Glass Foil"` or `"K♥ Stone Red Seal"`
+///
+/// # Safety
+/// `round` must come from `ortalab_round_new` or `ortalab_round_from_json`, `card` must be a
+/// NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ortalab_round_add_played(
+    round: *mut OrtalabRound,
+    card: *const c_char
+) -> OrtalabStatus {
+    push(round, card, |r| &mut r.cards_played)
+}
+
+/// Add a card held in hand
+///
+/// # Safety
+/// `round` must come from `ortalab_round_new` or `ortalab_round_from_json`, `card` must be a
+/// NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ortalab_round_add_held(
+    round: *mut OrtalabRound,
+    card: *const c_char
+) -> OrtalabStatus {
+    push(round, card, |r| &mut r.cards_held_in_hand)
+}
+
+/// Add a joker, e.g. `"Baron Polychrome"`
+///
+/// # Safety
+/// `round` must come from `ortalab_round_new` or `ortalab_round_from_json`, `joker` must be a
+/// NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ortalab_round_add_joker(
+    round: *mut OrtalabRound,
+    joker: *const c_char
+) -> OrtalabStatus {
+    push(round, joker, |r| &mut r.jokers)
+}
+
+/// Set the money at the start of the round, `0` by default
+///
+/// # Safety
+/// `round` must come from `ortalab_round_new` or `ortalab_round_from_json`.
+#[no_mangle]
+pub unsafe extern "C" fn ortalab_round_set_money(
+    round: *mut OrtalabRound,
+    money: i64
+) -> OrtalabStatus {
+    let Some(round) = round.as_mut() else {
+        return OrtalabStatus::NullPointer;
+    };
+    round.input.money = money;
+    OrtalabStatus::Ok
+}
+
+/// Score with exact rational arithmetic instead of floating point, off by default
+///
+/// # Safety
+/// `round` must come from `ortalab_round_new` or `ortalab_round_from_json`.
+#[no_mangle]
+pub unsafe extern "C" fn ortalab_round_set_exact(
+    round: *mut OrtalabRound,
+    exact: bool
+) -> OrtalabStatus {
+    let Some(round) = round.as_mut() else {
+        return OrtalabStatus::NullPointer;
+    };
+    round.arithmetic = if exact { Arithmetic::Exact } else { Arithmetic::Float };
+    OrtalabStatus::Ok
+}
+
+/// Check the round can actually be played: `InvalidRound` if not, e.g. with more
+/// jokers than joker slots
+///
+/// # Safety
+/// `round` must come from `ortalab_round_new` or `ortalab_round_from_json`.
+#[no_mangle]
+pub unsafe extern "C" fn ortalab_round_validate(round: *const OrtalabRound) -> OrtalabStatus {
+    match round.as_ref() {
+        None => OrtalabStatus::NullPointer,
+        Some(round) if round.input.validate().is_err() => OrtalabStatus::InvalidRound,
+        Some(_) => OrtalabStatus::Ok,
+    }
+}
+
+/// Score the round; the round is left untouched and can be scored again
+///
+/// # Safety
+/// `round` must come from `ortalab_round_new` or `ortalab_round_from_json`. Returns
+/// NULL if `round` is NULL or fails `ortalab_round_validate`.
+#[no_mangle]
+pub unsafe extern "C" fn ortalab_round_score(round: *const OrtalabRound) -> *mut OrtalabScore {
+    let Some(round) = round.as_ref() else {
+        return ptr::null_mut();
+    };
+    if round.input.validate().is_err() {
+        return ptr::null_mut();
+    }
+    let mut score = Score::with_arithmetic(round.input.clone(), round.arithmetic);
+    score.calculate_score();
+
+    Box::into_raw(
+        Box::new(OrtalabScore {
+            score: score.total().to_f64(),
+            exact_score: CString::new(score.total().to_string()).unwrap_or_default(),
+            rejection: score
+                .rejection()
+                .map(|rejection| CString::new(rejection.to_string()).unwrap_or_default()),
+            chips: score.chips.to_f64(),
+            mult: score.mult.to_f64(),
+            money_delta: score.money_delta(),
+            trace: score
+                .trace()
+                .iter()
+                .map(|entry| CString::new(entry.message.replace('\0', "")).unwrap_or_default())
+                .collect(),
+        })
+    )
+}
+
+/// Release a round
+///
+/// # Safety
+/// `round` must come from `ortalab_round_new` or `ortalab_round_from_json` and not be used
+/// afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn ortalab_round_free(round: *mut OrtalabRound) {
+    if !round.is_null() {
+        drop(Box::from_raw(round));
+    }
+}
+
+/// Final score, chips times mult rounded down
+///
+/// # Safety
+/// `score` must come from `ortalab_round_score`.
+#[no_mangle]
+pub unsafe extern "C" fn ortalab_score_total(score: *const OrtalabScore) -> f64 {
+    score.as_ref().map_or(0.0, |s| s.score)
+}
+
+/// Final score written out in full, every digit right after `ortalab_round_set_exact`;
+/// owned by `score`, NULL if `score` is NULL
+///
+/// # Safety
+/// `score` must come from `ortalab_round_score`.
+#[no_mangle]
+pub unsafe extern "C" fn ortalab_score_exact_total(score: *const OrtalabScore) -> *const c_char {
+    score.as_ref().map_or(ptr::null(), |s| s.exact_score.as_ptr())
+}
+
+/// Why the blind did not allow the hand, which then scored nothing; NULL when it was
+/// allowed, owned by `score`
+///
+/// # Safety
+/// `score` must come from `ortalab_round_score`.
+#[no_mangle]
+pub unsafe extern "C" fn ortalab_score_rejection(score: *const OrtalabScore) -> *const c_char {
+    score
+        .as_ref()
+        .and_then(|s| s.rejection.as_ref())
+        .map_or(ptr::null(), |rejection| rejection.as_ptr())
+}
+
+/// Chips after scoring
+///
+/// # Safety
+/// `score` must come from `ortalab_round_score`.
+#[no_mangle]
+pub unsafe extern "C" fn ortalab_score_chips(score: *const OrtalabScore) -> f64 {
+    score.as_ref().map_or(0.0, |s| s.chips)
+}
+
+/// Mult after scoring
+///
+/// # Safety
+/// `score` must come from `ortalab_round_score`.
+#[no_mangle]
+pub unsafe extern "C" fn ortalab_score_mult(score: *const OrtalabScore) -> f64 {
+    score.as_ref().map_or(0.0, |s| s.mult)
+}
+
+/// Money earned during the round, negative when money was spent
+///
+/// # Safety
+/// `score` must come from `ortalab_round_score`.
+#[no_mangle]
+pub unsafe extern "C" fn ortalab_score_money_delta(score: *const OrtalabScore) -> i64 {
+    score.as_ref().map_or(0, |s| s.money_delta)
+}
+
+/// Number of trace entries
+///
+/// # Safety
+/// `score` must come from `ortalab_round_score`.
+#[no_mangle]
+pub unsafe extern "C" fn ortalab_score_trace_len(score: *const OrtalabScore) -> usize {
+    score.as_ref().map_or(0, |s| s.trace.len())
+}
+
+/// Trace entry `index`, or NULL when out of range; owned by `score`
+///
+/// # Safety
+/// `score` must come from `ortalab_round_score`.
+#[no_mangle]
+pub unsafe extern "C" fn ortalab_score_trace_entry(
+    score: *const OrtalabScore,
+    index: usize
+) -> *const c_char {
+    score
+        .as_ref()
+        .and_then(|s| s.trace.get(index))
+        .map_or(ptr::null(), |line| line.as_ptr())
+}
+
+/// Release a score and its trace entries
+///
+/// # Safety
+/// `score` must come from `ortalab_round_score` and not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn ortalab_score_free(score: *mut OrtalabScore) {
+    if !score.is_null() {
+        drop(Box::from_raw(score));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_json(json: &str) -> Result<*mut OrtalabRound, OrtalabStatus> {
+        let json = CString::new(json).unwrap();
+        let mut round = ptr::null_mut();
+        match unsafe { ortalab_round_from_json(json.as_ptr(), &mut round) } {
+            OrtalabStatus::Ok => Ok(round),
+            status => Err(status),
+        }
+    }
+
+    #[test]
+    fn json_rounds_reach_every_field() {
+        // The Flint halves Pair (10, 2) to (5, 1): (5 + 10 + 10) x 1
+        let json = r#"{ "cards_played": ["K♥", "K♦"], "blind": "The Flint" }"#;
+        let round = from_json(json).unwrap();
+        unsafe {
+            assert_eq!(ortalab_round_validate(round), OrtalabStatus::Ok);
+            let score = ortalab_round_score(round);
+            assert_eq!(ortalab_score_total(score), 25.0);
+            assert!(ortalab_score_rejection(score).is_null());
+            ortalab_score_free(score);
+            ortalab_round_free(round);
+        }
+    }
+
+    #[test]
+    fn rejected_plays_say_why() {
+        let json = r#"{ "cards_played": ["K♥", "K♦"], "blind": "The Psychic" }"#;
+        let round = from_json(json).unwrap();
+        unsafe {
+            let score = ortalab_round_score(round);
+            let rejection = CStr::from_ptr(ortalab_score_rejection(score));
+            assert_eq!(rejection.to_str().unwrap(), "must play 5 cards, played 2");
+            ortalab_score_free(score);
+            ortalab_round_free(round);
+        }
+    }
+
+    #[test]
+    fn unknown_keys_and_invalid_rounds_are_refused() {
+        assert_eq!(from_json(r#"{ "blnd": "The Flint" }"#), Err(OrtalabStatus::ParseError));
+
+        // six cards played with a round context
+        let json = r#"{
+            "cards_played": ["A♠", "K♠", "Q♠", "J♠", "10♠", "9♠"],
+            "context": { "hands_left": 1, "discards_left": 0, "hand_size": 8, "deck_size": 40 }
+        }"#;
+        let round = from_json(json).unwrap();
+        unsafe {
+            assert_eq!(ortalab_round_validate(round), OrtalabStatus::InvalidRound);
+            assert!(ortalab_round_score(round).is_null());
+            ortalab_round_free(round);
+        }
+    }
+}
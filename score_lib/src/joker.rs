@@ -0,0 +1,362 @@
+use crate::card::Edition;
+use serde::{ de, Deserialize, Deserializer, Serialize, Serializer };
+use std::{ fmt, str::FromStr };
+
+/// Joker effects, a superset of `ortalib::Joker`
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Joker {
+    Joker,
+    JollyJoker,
+    ZanyJoker,
+    MadJoker,
+    CrazyJoker,
+    DrollJoker,
+    SlyJoker,
+    WilyJoker,
+    CleverJoker,
+    DeviousJoker,
+    CraftyJoker,
+    AbstractJoker,
+    RaisedFist,
+    Blackboard,
+    Baron,
+    GreedyJoker,
+    LustyJoker,
+    WrathfulJoker,
+    GluttonousJoker,
+    Fibonacci,
+    ScaryFace,
+    EvenSteven,
+    OddTodd,
+    Photograph,
+    SmileyFace,
+    FlowerPot,
+    FourFingers,
+    Shortcut,
+    Mime,
+    Pareidolia,
+    Splash,
+    SockAndBuskin,
+    SmearedJoker,
+    Blueprint,
+    /// +2 Chips for each $1 you have
+    Bull,
+    /// +2 Mult for every $5 you have
+    Bootstraps,
+    /// Earn $4 at the end of the round
+    GoldenJoker,
+    /// Played face cards have a 1 in 2 chance to give $2 when scored
+    BusinessCard,
+    /// +1 Mult per consecutive hand played without a scoring face card
+    RideTheBus,
+    /// +1 Mult per hand played
+    GreenJoker,
+    /// +100 Chips, -5 Chips for every hand played
+    IceCream,
+    /// Adds the number of times the poker hand has been played this run to Mult
+    Supernova,
+    /// Gains +15 Chips if the played hand contains a Straight
+    Runner,
+    /// Gains +4 Chips if the played hand has exactly 4 cards
+    SquareJoker,
+    /// X3 Mult if the poker hand has already been played this round
+    CardSharp,
+    /// Gains X0.2 Mult per consecutive hand played without playing your most played poker hand
+    Obelisk,
+    /// +30 Chips for each remaining discard
+    Banner,
+    /// +15 Mult when 0 discards remaining
+    MysticSummit,
+    /// +2 Chips for each remaining card in deck
+    BlueJoker,
+    /// X3 Mult on final hand of round
+    Acrobat,
+    /// Gives X0.2 Mult for each Steel Card in your full deck
+    SteelJoker,
+    /// Gives +25 Chips for each Stone Card in your full deck
+    StoneJoker,
+    /// Earn $1 for each 9 in your full deck at end of round
+    CloudNine,
+    /// +4 Mult for each card below the starting deck size in your full deck
+    Erosion,
+    /// +20 Mult if played hand contains 3 or fewer cards
+    HalfJoker,
+    /// +250 Chips, -2 hand size
+    Stuntman,
+    /// +1 hand size
+    Juggler,
+    /// Each played 10 or 4 gives +10 Chips and +4 Mult when scored
+    WalkieTalkie,
+    /// Played Aces give +20 Chips and +4 Mult when scored
+    Scholar,
+    /// Played Kings and Queens each give X2 Mult when scored
+    Triboulet,
+    /// Played cards with Spade suit give +50 Chips when scored
+    Arrowhead,
+    /// Played cards with Club suit give +7 Mult when scored
+    OnyxAgate,
+    /// Played cards with Diamond suit earn $1 when scored
+    RoughGem,
+    /// 1 in 2 chance for played cards with Heart suit to give X1.5 Mult when scored
+    Bloodstone,
+    /// Retrigger each played 2, 3, 4, or 5
+    Hack,
+    /// Each Queen held in hand gives +13 Mult
+    ShootTheMoon,
+    /// Each face card held in hand has a 1 in 2 chance to give $1
+    ReservedParking,
+    /// Uncommon Jokers each give X1.5 Mult
+    BaseballCard,
+    /// Adds the sell value of all other owned Jokers to Mult
+    Swashbuckler,
+    /// X1 Mult for each empty Joker slot, Joker Stencil included
+    JokerStencil,
+}
+
+/// Every joker with the name used in round files, its rarity and base cost
+#[rustfmt::skip]
+const JOKERS: &[(Joker, &str, Rarity, u32)] = &[
+    (Joker::Joker,           "Joker",            Rarity::Common,     2),
+    (Joker::JollyJoker,      "Jolly Joker",      Rarity::Common,     3),
+    (Joker::ZanyJoker,       "Zany Joker",       Rarity::Common,     4),
+    (Joker::MadJoker,        "Mad Joker",        Rarity::Common,     4),
+    (Joker::CrazyJoker,      "Crazy Joker",      Rarity::Common,     4),
+    (Joker::DrollJoker,      "Droll Joker",      Rarity::Common,     4),
+    (Joker::SlyJoker,        "Sly Joker",        Rarity::Common,     3),
+    (Joker::WilyJoker,       "Wily Joker",       Rarity::Common,     4),
+    (Joker::CleverJoker,     "Clever Joker",     Rarity::Common,     4),
+    (Joker::DeviousJoker,    "Devious Joker",    Rarity::Common,     4),
+    (Joker::CraftyJoker,     "Crafty Joker",     Rarity::Common,     4),
+    (Joker::AbstractJoker,   "Abstract Joker",   Rarity::Common,     4),
+    (Joker::RaisedFist,      "Raised Fist",      Rarity::Common,     5),
+    (Joker::Blackboard,      "Blackboard",       Rarity::Uncommon,   6),
+    (Joker::Baron,           "Baron",            Rarity::Rare,       8),
+    (Joker::GreedyJoker,     "Greedy Joker",     Rarity::Common,     5),
+    (Joker::LustyJoker,      "Lusty Joker",      Rarity::Common,     5),
+    (Joker::WrathfulJoker,   "Wrathful Joker",   Rarity::Common,     5),
+    (Joker::GluttonousJoker, "Gluttonous Joker", Rarity::Common,     5),
+    (Joker::Fibonacci,       "Fibonacci",        Rarity::Uncommon,   8),
+    (Joker::ScaryFace,       "Scary Face",       Rarity::Common,     4),
+    (Joker::EvenSteven,      "Even Steven",      Rarity::Common,     4),
+    (Joker::OddTodd,         "Odd Todd",         Rarity::Common,     4),
+    (Joker::Photograph,      "Photograph",       Rarity::Common,     5),
+    (Joker::SmileyFace,      "Smiley Face",      Rarity::Common,     4),
+    (Joker::FlowerPot,       "Flower Pot",       Rarity::Uncommon,   6),
+    (Joker::FourFingers,     "Four Fingers",     Rarity::Uncommon,   7),
+    (Joker::Shortcut,        "Shortcut",         Rarity::Uncommon,   7),
+    (Joker::Mime,            "Mime",             Rarity::Uncommon,   5),
+    (Joker::Pareidolia,      "Pareidolia",       Rarity::Uncommon,   5),
+    (Joker::Splash,          "Splash",           Rarity::Common,     3),
+    (Joker::SockAndBuskin,   "Sock And Buskin",  Rarity::Uncommon,   6),
+    (Joker::SmearedJoker,    "Smeared Joker",    Rarity::Uncommon,   7),
+    (Joker::Blueprint,       "Blueprint",        Rarity::Rare,      10),
+    (Joker::Bull,            "Bull",             Rarity::Uncommon,   6),
+    (Joker::Bootstraps,      "Bootstraps",       Rarity::Uncommon,   7),
+    (Joker::GoldenJoker,     "Golden Joker",     Rarity::Common,     6),
+    (Joker::BusinessCard,    "Business Card",    Rarity::Common,     4),
+    (Joker::RideTheBus,      "Ride the Bus",     Rarity::Common,     6),
+    (Joker::GreenJoker,      "Green Joker",      Rarity::Common,     4),
+    (Joker::IceCream,        "Ice Cream",        Rarity::Common,     5),
+    (Joker::Supernova,       "Supernova",        Rarity::Common,     5),
+    (Joker::Runner,          "Runner",           Rarity::Common,     5),
+    (Joker::SquareJoker,     "Square Joker",     Rarity::Common,     4),
+    (Joker::CardSharp,       "Card Sharp",       Rarity::Uncommon,   6),
+    (Joker::Obelisk,         "Obelisk",          Rarity::Rare,       8),
+    (Joker::Banner,          "Banner",           Rarity::Common,     5),
+    (Joker::MysticSummit,    "Mystic Summit",    Rarity::Common,     5),
+    (Joker::BlueJoker,       "Blue Joker",       Rarity::Common,     5),
+    (Joker::Acrobat,         "Acrobat",          Rarity::Uncommon,   6),
+    (Joker::SteelJoker,      "Steel Joker",      Rarity::Uncommon,   7),
+    (Joker::StoneJoker,      "Stone Joker",      Rarity::Uncommon,   6),
+    (Joker::CloudNine,       "Cloud 9",          Rarity::Uncommon,   7),
+    (Joker::Erosion,         "Erosion",          Rarity::Uncommon,   6),
+    (Joker::HalfJoker,       "Half Joker",       Rarity::Common,     5),
+    (Joker::Stuntman,        "Stuntman",         Rarity::Rare,       7),
+    (Joker::Juggler,         "Juggler",          Rarity::Common,     4),
+    (Joker::WalkieTalkie,    "Walkie Talkie",    Rarity::Common,     4),
+    (Joker::Scholar,         "Scholar",          Rarity::Common,     4),
+    (Joker::Triboulet,       "Triboulet",        Rarity::Legendary, 20),
+    (Joker::Arrowhead,       "Arrowhead",        Rarity::Uncommon,   7),
+    (Joker::OnyxAgate,       "Onyx Agate",       Rarity::Uncommon,   7),
+    (Joker::RoughGem,        "Rough Gem",        Rarity::Uncommon,   7),
+    (Joker::Bloodstone,      "Bloodstone",       Rarity::Uncommon,   7),
+    (Joker::Hack,            "Hack",             Rarity::Uncommon,   6),
+    (Joker::ShootTheMoon,    "Shoot the Moon",   Rarity::Common,     5),
+    (Joker::ReservedParking, "Reserved Parking", Rarity::Common,     6),
+    (Joker::BaseballCard,    "Baseball Card",    Rarity::Rare,       8),
+    (Joker::Swashbuckler,    "Swashbuckler",     Rarity::Common,     4),
+    (Joker::JokerStencil,    "Joker Stencil",    Rarity::Uncommon,   8),
+];
+
+/// How rare a joker is in the shop
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+    Legendary,
+}
+
+impl Joker {
+    fn info(&self) -> &'static (Joker, &'static str, Rarity, u32) {
+        JOKERS.iter()
+            .find(|(joker, ..)| joker == self)
+            .expect("every joker is in the table")
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.info().1
+    }
+
+    pub fn rarity(&self) -> Rarity {
+        self.info().2
+    }
+
+    /// Shop price without an edition
+    pub fn base_cost(&self) -> u32 {
+        self.info().3
+    }
+}
+
+impl From<ortalib::Joker> for Joker {
+    /// ortalib names its jokers the same way, so go through the name
+    fn from(joker: ortalib::Joker) -> Self {
+        joker.to_string().parse().expect("ortalib jokers are a subset")
+    }
+}
+
+impl FromStr for Joker {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        JOKERS.iter()
+            .find(|(_, name, ..)| *name == s)
+            .map(|(joker, ..)| *joker)
+            .ok_or_else(|| format!("Invalid Joker: `{s}`"))
+    }
+}
+
+impl fmt::Display for Joker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl fmt::Debug for Joker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// What a scaling joker carries from one hand to the next
+///
+/// Each joker only uses the fields it needs; missing fields read as zero.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct JokerState {
+    /// Chips gained so far, e.g. Runner and Square Joker, or left, e.g. Ice Cream
+    pub chips: i64,
+    /// Mult gained so far, e.g. Ride the Bus and Green Joker
+    pub mult: i64,
+    /// Hands counted so far, e.g. Obelisk's streak
+    pub count: i64,
+}
+
+/// A joker as `score_lib` models it: an `ortalib::JokerCard` over the larger
+/// `Joker` set
+///
+/// Parsed from the same text as `ortalib::JokerCard`, with an optional trailing
+/// debuff flag, e.g. `Baron Polychrome Debuffed`.
+/// Joker cards compare by value, unlike `ortalib::JokerCard`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JokerCard {
+    pub joker: Joker,
+    pub edition: Option<Edition>,
+    /// Does nothing at all, not even its edition
+    pub debuffed: bool,
+}
+
+impl JokerCard {
+    pub fn new(joker: Joker, edition: Option<Edition>) -> Self {
+        JokerCard { joker, edition, debuffed: false }
+    }
+}
+
+impl JokerCard {
+    /// Shop price, the base cost plus the edition's extra cost
+    pub fn cost(&self) -> u32 {
+        let extra = match self.edition {
+            Some(Edition::Foil) => 2,
+            Some(Edition::Holographic) => 3,
+            Some(Edition::Polychrome) | Some(Edition::Negative) => 5,
+            None => 0,
+        };
+        self.joker.base_cost() + extra
+    }
+
+    /// Half the cost rounded down, at least $1
+    pub fn sell_value(&self) -> u32 {
+        (self.cost() / 2).max(1)
+    }
+}
+
+impl From<ortalib::JokerCard> for JokerCard {
+    fn from(card: ortalib::JokerCard) -> Self {
+        JokerCard::new(card.joker.into(), card.edition.map(Edition::from))
+    }
+}
+
+impl FromStr for JokerCard {
+    type Err = String;
+
+    /// `<joker> [edition] [Debuffed]`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rest, debuffed) = match s.strip_suffix(" Debuffed") {
+            Some(rest) => (rest.trim(), true),
+            None => (s, false),
+        };
+        let mut joker_str = rest;
+        let mut edition = None;
+        for possible in [Edition::Foil, Edition::Holographic, Edition::Polychrome, Edition::Negative] {
+            if let Some(leftover) = rest.strip_suffix(&possible.to_string()) {
+                joker_str = leftover.trim();
+                edition = Some(possible);
+                break;
+            }
+        }
+        let joker = joker_str.parse().map_err(|err| format!("Invalid JokerCard `{s}`: {err}"))?;
+        Ok(JokerCard { joker, edition, debuffed })
+    }
+}
+
+impl fmt::Display for JokerCard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.joker)?;
+        if let Some(edition) = self.edition {
+            write!(f, " {edition}")?;
+        }
+        if self.debuffed {
+            write!(f, " Debuffed")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for JokerCard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Serialize for JokerCard {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for JokerCard {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
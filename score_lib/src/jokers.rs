@@ -1,10 +1,9 @@
-use std::collections::{ HashSet };
-
-use crate::card::CardUtils;
-use crate::utils::{ num_of_poker_by_suits };
-use crate::{ explain, Score };
-use crate::modifiers::EditionUtils;
-use ortalib::{ Card, Chips, Joker, JokerCard, Mult, PokerHand, Rank, Suit };
+use crate::card::{ CardId, CardUtils, Edition, Enhancement };
+use crate::condition::{ Condition, Count };
+use crate::card_trigger::{ CardEffect, CardPredicate, OnHeld, OnScored };
+use crate::{ explain, Joker, JokerCard, JokerState, Number, Rarity, Score };
+use crate::getters::EditionValueGetter;
+use ortalib::{ PokerHand, Rank, Suit };
 
 // pub enum JokerType {
 //     OnScored,
@@ -12,20 +11,61 @@ use ortalib::{ Card, Chips, Joker, JokerCard, Mult, PokerHand, Rank, Suit };
 //     Independent,
 // }
 
+/// Deck size Erosion counts missing cards from
+const STARTING_DECK_SIZE: usize = 52;
+
+/// Which cards held in hand a held-in-hand joker acts on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeldTarget {
+    /// Every card held in hand, the joker checks the card itself
+    Each,
+    /// Only the lowest ranked card held, the rightmost one on ties, never a Stone card
+    LowestRank,
+}
+
+/// One step a joker takes in the joker phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JokerStep {
+    /// The joker's edition bonus
+    Edition,
+    /// The joker's own effect, only for independent jokers
+    Effect,
+    /// Other jokers reacting to this one, e.g. Baseball Card
+    Reactions,
+}
+
 pub trait JokerUtils {
-    fn joker_value(&self, s: &Score) -> (Chips, Mult, String);
+    fn joker_value(&self, s: &Score) -> (Number, Number, String);
     fn meet_condition(&self, s: &mut Score) -> bool;
+    /// What the joker's effect needs to trigger
+    fn condition(&self) -> Condition;
+    /// What a scaling joker needs to grow, e.g. Runner needs a Straight
+    fn upgrade_condition(&self) -> Condition;
+    /// Change to the hand size while the joker is held, e.g. Juggler
+    fn hand_size_delta(&self) -> i64;
     fn is_on_scored(&self) -> bool;
+    /// The predicate and effects of an on-scored joker
+    fn on_scored(&self) -> Option<OnScored>;
     fn is_on_held(&self) -> bool;
     fn is_dependent(&self) -> bool;
-    fn apply_independent(&self, s: &mut Score) -> ();
-    fn apply_on_scored(&self, s: &mut Score, card: &Card) -> ();
-    fn apply_on_held(&self, s: &mut Score, card: &Card, is_last: bool);
+    fn apply_independent(&self, s: &mut Score);
+    fn joker_steps(&self) -> Vec<JokerStep>;
+    fn apply_edition(&self, s: &mut Score);
+    fn apply_joker_phase(&self, s: &mut Score);
+    fn apply_on_scored(&self, s: &mut Score, id: CardId);
+    /// React to `other` in its joker phase, after its effect, e.g. Baseball Card
+    fn apply_on_other_joker(&self, s: &mut Score, other: &JokerCard);
+    /// The target, predicate and effects of a held-in-hand joker
+    fn on_held(&self) -> Option<OnHeld>;
+    fn held_target(&self) -> HeldTarget;
+    fn apply_on_held(&self, s: &mut Score, id: CardId);
+    fn initial_state(&self) -> JokerState;
+    fn update_state(&self, s: &mut Score);
 }
 
 impl JokerUtils for JokerCard {
     fn is_on_held(&self) -> bool {
-        matches!(self.joker, Joker::RaisedFist | Joker::Baron)
+        self.on_held().is_some()
     }
     fn is_dependent(&self) -> bool {
         matches!(
@@ -43,28 +83,130 @@ impl JokerUtils for JokerCard {
                 Joker::CraftyJoker |
                 Joker::AbstractJoker |
                 Joker::Blackboard |
-                Joker::FlowerPot
+                Joker::FlowerPot |
+                Joker::Bull |
+                Joker::Bootstraps |
+                Joker::GoldenJoker |
+                Joker::RideTheBus |
+                Joker::GreenJoker |
+                Joker::IceCream |
+                Joker::Supernova |
+                Joker::Runner |
+                Joker::SquareJoker |
+                Joker::CardSharp |
+                Joker::Obelisk |
+                Joker::Banner |
+                Joker::MysticSummit |
+                Joker::BlueJoker |
+                Joker::Acrobat |
+                Joker::SteelJoker |
+                Joker::StoneJoker |
+                Joker::CloudNine |
+                Joker::Erosion |
+                Joker::HalfJoker |
+                Joker::Stuntman |
+                Joker::Swashbuckler |
+                Joker::JokerStencil
         )
     }
     fn is_on_scored(&self) -> bool {
-        matches!(
-            self.joker,
-            Joker::GreedyJoker |
-                Joker::LustyJoker |
-                Joker::WrathfulJoker |
-                Joker::GluttonousJoker |
-                Joker::Fibonacci |
-                Joker::ScaryFace |
-                Joker::EvenSteven |
-                Joker::OddTodd |
-                Joker::Photograph |
-                Joker::SmileyFace
-        )
+        self.on_scored().is_some()
     }
-    fn apply_independent(&self, s: &mut Score) -> () {
+    fn on_scored(&self) -> Option<OnScored> {
+        use CardEffect::*;
+        use CardPredicate::{ Face, FirstFace, Ranks };
+
+        let on_scored = match self.joker {
+            Joker::GreedyJoker => OnScored::new(CardPredicate::Suit(Suit::Diamonds), vec![Mult(3.0)]),
+            Joker::LustyJoker => OnScored::new(CardPredicate::Suit(Suit::Hearts), vec![Mult(3.0)]),
+            Joker::WrathfulJoker => OnScored::new(CardPredicate::Suit(Suit::Spades), vec![Mult(3.0)]),
+            Joker::GluttonousJoker => OnScored::new(CardPredicate::Suit(Suit::Clubs), vec![Mult(3.0)]),
+            Joker::Fibonacci => {
+                let fibonacci_ranks = vec![Rank::Ace, Rank::Two, Rank::Three, Rank::Five, Rank::Eight];
+                OnScored::new(Ranks(fibonacci_ranks), vec![Mult(8.0)])
+            }
+            Joker::ScaryFace => OnScored::new(Face, vec![Chips(30.0)]),
+            Joker::EvenSteven => {
+                let even_ranks = vec![Rank::Two, Rank::Four, Rank::Six, Rank::Eight, Rank::Ten];
+                OnScored::new(Ranks(even_ranks), vec![Mult(4.0)])
+            }
+            Joker::OddTodd => {
+                let odd_ranks = vec![Rank::Ace, Rank::Three, Rank::Five, Rank::Seven, Rank::Nine];
+                OnScored::new(Ranks(odd_ranks), vec![Chips(31.0)])
+            }
+            // only the first face card played, but every time it scores
+            Joker::Photograph => OnScored::new(FirstFace, vec![XMult(2.0)]),
+            Joker::SmileyFace => OnScored::new(Face, vec![Mult(5.0)]),
+            Joker::BusinessCard => OnScored::new(Face, vec![Chance(1, 2, Box::new(Money(2)))]),
+            Joker::WalkieTalkie => {
+                OnScored::new(Ranks(vec![Rank::Ten, Rank::Four]), vec![Chips(10.0), Mult(4.0)])
+            }
+            Joker::Scholar => OnScored::new(Ranks(vec![Rank::Ace]), vec![Chips(20.0), Mult(4.0)]),
+            Joker::Triboulet => OnScored::new(Ranks(vec![Rank::King, Rank::Queen]), vec![XMult(2.0)]),
+            Joker::Arrowhead => OnScored::new(CardPredicate::Suit(Suit::Spades), vec![Chips(50.0)]),
+            Joker::OnyxAgate => OnScored::new(CardPredicate::Suit(Suit::Clubs), vec![Mult(7.0)]),
+            Joker::RoughGem => OnScored::new(CardPredicate::Suit(Suit::Diamonds), vec![Money(1)]),
+            Joker::Bloodstone => {
+                let effect = Chance(1, 2, Box::new(XMult(1.5)));
+                OnScored::new(CardPredicate::Suit(Suit::Hearts), vec![effect])
+            }
+            Joker::Hack => {
+                let low_ranks = vec![Rank::Two, Rank::Three, Rank::Four, Rank::Five];
+                OnScored::new(Ranks(low_ranks), vec![Retrigger])
+            }
+            _ => return None,
+        };
+        Some(on_scored)
+    }
+    /// Foil and Holographic trigger before the joker's own effect, Polychrome after
+    /// the other jokers have reacted to it.
+    /// Every joker with an edition gets its edition step, whatever its type and
+    /// whether or not its effect triggers.
+    fn joker_steps(&self) -> Vec<JokerStep> {
+        let mut steps = Vec::new();
+        if self.is_dependent() {
+            steps.push(JokerStep::Effect);
+        }
+        steps.push(JokerStep::Reactions);
+        match self.edition {
+            Some(Edition::Polychrome) => steps.push(JokerStep::Edition),
+            Some(Edition::Foil) | Some(Edition::Holographic) => steps.insert(0, JokerStep::Edition),
+            // Negative only adds a joker slot
+            Some(Edition::Negative) | None => (),
+        }
+        steps
+    }
+    fn apply_edition(&self, s: &mut Score) {
+        if let Some(e) = self.edition {
+            let (c, m, msg) = e.eidtion_value(&s.mult);
+            s.chips += c;
+            s.mult += m;
+            explain!(s, "🃏{:?} {} {:?}", &self, msg, (&s.chips, &s.mult));
+        }
+    }
+    fn apply_joker_phase(&self, s: &mut Score) {
+        if self.debuffed {
+            explain!(s, "🃏{:?} debuffed", &self.joker);
+            return;
+        }
+        for step in self.joker_steps() {
+            match step {
+                JokerStep::Edition => self.apply_edition(s),
+                JokerStep::Effect => self.apply_independent(s),
+                JokerStep::Reactions => {
+                    let current = s.current_joker;
+                    for (i, other) in s.jokers.clone().iter().enumerate() {
+                        s.current_joker = Some(i);
+                        other.apply_on_other_joker(s, self);
+                    }
+                    s.current_joker = current;
+                }
+            }
+        }
+    }
+    fn apply_independent(&self, s: &mut Score) {
         if !self.meet_condition(s) {
-            explain!("🃏{:?}", &self);
-            self.edition.apply(s);
+            explain!(s, "🃏{:?} not triggered", &self.joker);
             return;
         }
 
@@ -82,167 +224,484 @@ impl JokerUtils for JokerCard {
             | Joker::CraftyJoker
             | Joker::AbstractJoker
             | Joker::Blackboard
-            | Joker::FlowerPot => {
+            | Joker::FlowerPot
+            | Joker::Bull
+            | Joker::Bootstraps
+            | Joker::RideTheBus
+            | Joker::GreenJoker
+            | Joker::Supernova
+            | Joker::Runner
+            | Joker::SquareJoker
+            | Joker::CardSharp
+            | Joker::Obelisk
+            | Joker::Banner
+            | Joker::MysticSummit
+            | Joker::BlueJoker
+            | Joker::Acrobat
+            | Joker::SteelJoker
+            | Joker::StoneJoker
+            | Joker::Erosion
+            | Joker::HalfJoker
+            | Joker::Stuntman
+            | Joker::Swashbuckler
+            | Joker::JokerStencil => {
                 let (c, m, msg) = self.joker_value(s);
                 s.chips = c;
                 s.mult = m;
-                explain!("🃏{:?} {} {:?}", &self, msg, (s.chips, s.mult));
+                explain!(s, "🃏{:?} {} {:?}", &self.joker, msg, (&s.chips, &s.mult));
+            }
+            Joker::IceCream => {
+                let (c, m, msg) = self.joker_value(s);
+                s.chips = c;
+                s.mult = m;
+                explain!(s, "🃏{:?} {} {:?}", &self.joker, msg, (&s.chips, &s.mult));
+                let state = s.current_state();
+                state.chips = (state.chips - 5).max(0);
+                if state.chips == 0 {
+                    explain!(s, "🃏{:?} melted", &self.joker);
+                }
+            }
+            Joker::CloudNine => {
+                let nines = s.deck().map_or(0, |deck| deck.rank(Rank::Nine)) as i64;
+                s.earn(nines);
+                explain!(s, "🃏{:?} +${} (${})", &self.joker, nines, s.money());
+            }
+            Joker::GoldenJoker => {
+                s.earn(4);
+                explain!(s, "🃏{:?} +$4 (${})", &self.joker, s.money());
             }
             _ => panic!("joker not found"),
         }
-        self.edition.apply(s);
     }
 
-    fn apply_on_scored(&self, s: &mut Score, card: &Card) {
+    fn apply_on_other_joker(&self, s: &mut Score, other: &JokerCard) {
+        if self.debuffed || other.debuffed {
+            return;
+        }
+        if self.joker == Joker::BaseballCard && other.joker.rarity() == Rarity::Uncommon {
+            // X1.5, kept exact in exact arithmetic
+            s.mult = (s.mult.clone() * 3.0) / 2.0;
+            explain!(s, "🃏{:?} x1.5 Mult for {:?} {:?}", &self.joker, &other.joker, (&s.chips, &s.mult));
+        }
+    }
+    fn apply_on_scored(&self, s: &mut Score, id: CardId) {
+        if self.debuffed {
+            return;
+        }
+        let Some(on_scored) = self.on_scored() else {
+            return;
+        };
+        if on_scored.predicate.matches(s, id) {
+            on_scored.effects.iter().for_each(|effect| effect.apply(s, self, id));
+        }
+    }
+    fn on_held(&self) -> Option<OnHeld> {
+        use CardEffect::*;
+        use CardPredicate::{ Any, Face, Ranks };
+
+        let on_held = match self.joker {
+            Joker::RaisedFist => OnHeld::new(HeldTarget::LowestRank, Any, vec![RankMult(2.0)]),
+            Joker::Baron => OnHeld::new(HeldTarget::Each, Ranks(vec![Rank::King]), vec![XMult(1.5)]),
+            Joker::ShootTheMoon => OnHeld::new(HeldTarget::Each, Ranks(vec![Rank::Queen]), vec![Mult(13.0)]),
+            Joker::ReservedParking => {
+                OnHeld::new(HeldTarget::Each, Face, vec![Chance(1, 2, Box::new(Money(1)))])
+            }
+            _ => return None,
+        };
+        Some(on_held)
+    }
+    fn held_target(&self) -> HeldTarget {
+        self.on_held().map_or(HeldTarget::Each, |on_held| on_held.target)
+    }
+    /// Only called for cards matched by `held_target`, once per trigger of the card
+    fn apply_on_held(&self, s: &mut Score, id: CardId) {
+        if self.debuffed {
+            return;
+        }
+        let Some(on_held) = self.on_held() else {
+            return;
+        };
+        if on_held.predicate.matches(s, id) {
+            on_held.effects.iter().for_each(|effect| effect.apply(s, self, id));
+        }
+    }
+
+    fn meet_condition(&self, s: &mut Score) -> bool {
+        self.condition().evaluate(s)
+    }
+    fn condition(&self) -> Condition {
         match &self.joker {
-            Joker::GreedyJoker => {
-                if card.suit == Suit::Diamonds || card.is_wild() {
-                    s.mult += 3.0;
-                    explain!("🃏 {:?} +3 Mult", &self);
-                }
+            Joker::JollyJoker | Joker::SlyJoker => Condition::ContainsHand(PokerHand::Pair),
+            Joker::ZanyJoker | Joker::WilyJoker => Condition::ContainsHand(PokerHand::ThreeOfAKind),
+            Joker::MadJoker | Joker::CleverJoker => Condition::ContainsHand(PokerHand::TwoPair),
+            Joker::CrazyJoker | Joker::DeviousJoker => Condition::ContainsHand(PokerHand::Straight),
+            Joker::DrollJoker | Joker::CraftyJoker => Condition::ContainsHand(PokerHand::Flush),
+            Joker::HalfJoker => Condition::CardsPlayed(Count::AtMost(3)),
+            Joker::CardSharp => Condition::HandPlayedThisRound(Count::AtLeast(2)),
+            // without a round context these jokers have nothing to go on
+            Joker::Banner | Joker::BlueJoker => Condition::HasContext,
+            Joker::MysticSummit => Condition::DiscardsLeft(Count::Exactly(0)),
+            Joker::Acrobat => Condition::HandsLeft(Count::Exactly(0)),
+            // without a full deck these jokers have nothing to count
+            Joker::SteelJoker | Joker::StoneJoker | Joker::CloudNine | Joker::Erosion =>
+                Condition::HasFullDeck,
+            Joker::Blackboard => Condition::HeldOnlySuits(vec![Suit::Clubs, Suit::Spades]),
+            Joker::FlowerPot => Condition::ScoredEverySuit,
+            _ => Condition::Always,
+        }
+    }
+    fn upgrade_condition(&self) -> Condition {
+        match self.joker {
+            Joker::Runner => Condition::ContainsHand(PokerHand::Straight),
+            Joker::SquareJoker => Condition::CardsPlayed(Count::Exactly(4)),
+            _ => Condition::Always,
+        }
+    }
+    fn hand_size_delta(&self) -> i64 {
+        match self.joker {
+            Joker::Juggler => 1,
+            Joker::Stuntman => -2,
+            _ => 0,
+        }
+    }
+    fn joker_value(&self, s: &Score) -> (Number, Number, String) {
+        match self.joker {
+            Joker::Joker => (s.chips.clone(), s.mult.clone() + 4.0, "+4 Mult".to_string()),
+            Joker::JollyJoker => (s.chips.clone(), s.mult.clone() + 8.0, "+8 Mult".to_string()),
+            Joker::ZanyJoker => (s.chips.clone(), s.mult.clone() + 12.0, "+12 Mult".to_string()),
+            Joker::MadJoker => (s.chips.clone(), s.mult.clone() + 10.0, "+10 Mult".to_string()),
+            Joker::CrazyJoker => (s.chips.clone(), s.mult.clone() + 12.0, "+12 Mult".to_string()),
+            Joker::DrollJoker => (s.chips.clone(), s.mult.clone() + 10.0, "+10 Mult".to_string()),
+            Joker::SlyJoker => (s.chips.clone() + 50.0, s.mult.clone(), "+50 Chips".to_string()),
+            Joker::WilyJoker => (s.chips.clone() + 100.0, s.mult.clone(), "+100 Chips".to_string()),
+            Joker::CleverJoker => (s.chips.clone() + 80.0, s.mult.clone(), "+80 Chips".to_string()),
+            Joker::DeviousJoker => (s.chips.clone() + 100.0, s.mult.clone(), "+100 Chips".to_string()),
+            Joker::CraftyJoker => (s.chips.clone() + 80.0, s.mult.clone(), "+80 Chips".to_string()),
+            Joker::AbstractJoker => {
+                let n = s.jokers.len() as f64;
+                (s.chips.clone(), s.mult.clone() + 3.0 * n, format!("+3x{n} Mult"))
             }
-            Joker::LustyJoker => {
-                if card.suit == Suit::Hearts || card.is_wild() {
-                    s.mult += 3.0;
-                    explain!("🃏 {:?} +3 Mult", &self);
-                }
+            Joker::Blackboard => (s.chips.clone(), s.mult.clone() * 3.0, "x3 Mult".to_string()),
+            Joker::FlowerPot => (s.chips.clone(), s.mult.clone() * 3.0, "x3 Mult".to_string()),
+            Joker::Bull => {
+                let chips = 2 * s.money().max(0);
+                (s.chips.clone() + (chips as f64), s.mult.clone(), format!("+{chips} Chips"))
             }
-            Joker::WrathfulJoker => {
-                if card.suit == Suit::Spades || card.is_wild() {
-                    s.mult += 3.0;
-                    explain!("🃏 {:?} +3 Mult", &self);
-                }
+            Joker::Bootstraps => {
+                let mult = 2 * (s.money().max(0) / 5);
+                (s.chips.clone(), s.mult.clone() + (mult as f64), format!("+{mult} Mult"))
             }
-            Joker::GluttonousJoker => {
-                if card.suit == Suit::Clubs || card.is_wild() {
-                    s.mult += 3.0;
-                    explain!("🃏 {:?} +3 Mult", &self);
-                }
+            Joker::RideTheBus | Joker::GreenJoker => {
+                let mult = s.joker_state[s.current_joker.unwrap()].mult;
+                (s.chips.clone(), s.mult.clone() + (mult as f64), format!("+{mult} Mult"))
             }
-            Joker::Fibonacci => {
-                let fibonacci_ranks = [Rank::Ace, Rank::Two, Rank::Three, Rank::Five, Rank::Eight];
-                if fibonacci_ranks.contains(&card.rank) {
-                    s.mult += 8.0;
-                    explain!("🃏 {:?} +8 Mult", &self);
-                }
+            Joker::IceCream | Joker::Runner | Joker::SquareJoker => {
+                let chips = s.joker_state[s.current_joker.unwrap()].chips;
+                (s.chips.clone() + (chips as f64), s.mult.clone(), format!("+{chips} Chips"))
             }
-            Joker::ScaryFace => {
-                if card.rank.is_face() {
-                    s.chips += 30.0;
-                    explain!("🃏 {:?} +30 Chips", &self);
-                }
+            Joker::Supernova => {
+                let played = s.hand_history().run_plays(s.best_poker_hand());
+                (s.chips.clone(), s.mult.clone() + f64::from(played), format!("+{played} Mult"))
             }
-            Joker::EvenSteven => {
-                let even_ranks = [Rank::Two, Rank::Four, Rank::Six, Rank::Eight, Rank::Ten];
-                if even_ranks.contains(&card.rank) {
-                    s.mult += 4.0;
-                    explain!("🃏 {:?} +4 Mult", &self);
-                }
+            Joker::CardSharp => (s.chips.clone(), s.mult.clone() * 3.0, "x3 Mult".to_string()),
+            Joker::Banner => {
+                let chips = 30 * s.context().map_or(0, |c| c.discards_left);
+                (s.chips.clone() + f64::from(chips), s.mult.clone(), format!("+{chips} Chips"))
             }
-            Joker::OddTodd => {
-                let odd_ranks = [Rank::Ace, Rank::Three, Rank::Five, Rank::Seven, Rank::Nine];
-                if odd_ranks.contains(&card.rank) {
-                    s.chips += 31.0;
-                    explain!("🃏 {:?} +31 Chips", &self);
-                }
+            Joker::HalfJoker => (s.chips.clone(), s.mult.clone() + 20.0, "+20 Mult".to_string()),
+            Joker::Stuntman => (s.chips.clone() + 250.0, s.mult.clone(), "+250 Chips".to_string()),
+            Joker::MysticSummit => (s.chips.clone(), s.mult.clone() + 15.0, "+15 Mult".to_string()),
+            Joker::BlueJoker => {
+                let chips = 2 * s.context().map_or(0, |c| c.deck_size);
+                (s.chips.clone() + f64::from(chips), s.mult.clone(), format!("+{chips} Chips"))
             }
-            Joker::Photograph => {
-                if card.rank.is_face() && !s.photograph_triggered {
-                    s.mult *= 2.0;
-                    s.photograph_triggered = true;
-                    explain!("🃏 {:?} x2 Mult", &self);
-                }
+            Joker::Acrobat => (s.chips.clone(), s.mult.clone() * 3.0, "x3 Mult".to_string()),
+            Joker::SteelJoker => {
+                let steel = s.deck().map_or(0, |deck| deck.enhancement(Enhancement::Steel)) as f64;
+                // X(1 + 0.2 * steel), kept exact in exact arithmetic
+                let mult = (s.mult.clone() * (5.0 + steel)) / 5.0;
+                (s.chips.clone(), mult, format!("x{} Mult", 1.0 + steel / 5.0))
             }
-            Joker::SmileyFace => {
-                if card.rank.is_face() {
-                    s.mult += 5.0;
-                    explain!("🃏 {:?} +5 Mult", &self);
-                }
+            Joker::StoneJoker => {
+                let stone = s.deck().map_or(0, |deck| deck.enhancement(Enhancement::Stone)) as f64;
+                (s.chips.clone() + 25.0 * stone, s.mult.clone(), format!("+{} Chips", 25.0 * stone))
             }
-            _ => todo!(), // 其他Joker类型
+            Joker::Erosion => {
+                let missing = STARTING_DECK_SIZE.saturating_sub(s.deck().map_or(0, |deck| deck.size)) as f64;
+                (s.chips.clone(), s.mult.clone() + 4.0 * missing, format!("+{} Mult", 4.0 * missing))
+            }
+            Joker::Swashbuckler => {
+                let current = s.current_joker.unwrap();
+                let value: u32 = s.jokers
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| *i != current)
+                    .map(|(_, joker)| joker.sell_value())
+                    .sum();
+                (s.chips.clone(), s.mult.clone() + f64::from(value), format!("+{value} Mult"))
+            }
+            Joker::JokerStencil => {
+                // every Joker Stencil counts as an empty slot, itself included
+                let stencils = s.jokers
+                    .iter()
+                    .filter(|joker| joker.joker == Joker::JokerStencil)
+                    .count();
+                let empty = (s.joker_slots().saturating_sub(s.jokers.len()) + stencils) as f64;
+                (s.chips.clone(), s.mult.clone() * empty, format!("x{empty} Mult"))
+            }
+            Joker::Obelisk => {
+                let streak = s.joker_state[s.current_joker.unwrap()].count;
+                // X(1 + 0.2 * streak), kept exact in exact arithmetic
+                let mult = (s.mult.clone() * ((5 + streak) as f64)) / 5.0;
+                (s.chips.clone(), mult, format!("x{} Mult", 1.0 + (streak as f64) / 5.0))
+            }
+            _ => panic!("Joker type not found"),
         }
     }
-    fn apply_on_held(&self, s: &mut Score, card: &Card, is_last: bool) {
-        match &self.joker {
-            Joker::RaisedFist => {
-                // clone and sort reversed, once joker triggered, won't trigger again
-                if !s.raised_first_triggered {
-                    if is_last {
-                        s.mult += 2.0 * card.rank.rank_value();
-                        s.raised_first_triggered = true;
-                    }
+    fn initial_state(&self) -> JokerState {
+        match self.joker {
+            Joker::IceCream => JokerState { chips: 100, ..JokerState::default() },
+            _ => JokerState::default(),
+        }
+    }
+    /// Scaling jokers grow (or reset) once per hand, before the cards score
+    fn update_state(&self, s: &mut Score) {
+        if self.debuffed {
+            return;
+        }
+        match self.joker {
+            Joker::RideTheBus => {
+                let face_scored = s.scored().any(|card| !card.is_stone() && card.rank.is_face());
+                let state = s.current_state();
+                if face_scored {
+                    state.mult = 0;
+                    explain!(s, "🃏{:?} reset", &self.joker);
+                } else {
+                    state.mult += 1;
+                    explain!(s, "🃏{:?} upgraded to +{} Mult", &self.joker, state.mult);
                 }
             }
-            Joker::Baron => {
-                if card.rank == Rank::King {
-                    s.mult *= 1.5;
-                    explain!("🃏 {:?} x1.5 Mult", &self);
+            Joker::GreenJoker => {
+                let state = s.current_state();
+                state.mult += 1;
+                explain!(s, "🃏{:?} upgraded to +{} Mult", &self.joker, state.mult);
+            }
+            Joker::Obelisk => {
+                // ties with another hand do not count as most played
+                let hand = s.best_poker_hand();
+                let played = s.hand_history().run_plays(hand);
+                let most_played = s.hand_history().run
+                    .iter()
+                    .all(|(other, n)| *other == hand || *n < played);
+                let state = s.current_state();
+                if most_played {
+                    state.count = 0;
+                    explain!(s, "🃏{:?} reset", &self.joker);
+                } else {
+                    state.count += 1;
+                    explain!(s, "🃏{:?} upgraded to x{} Mult", &self.joker, 1.0 + (state.count as f64) / 5.0);
                 }
             }
-            _ => panic!("on held joker not found."),
+            Joker::Runner if self.upgrade_condition().evaluate(s) => {
+                let state = s.current_state();
+                state.chips += 15;
+                explain!(s, "🃏{:?} upgraded to +{} Chips", &self.joker, state.chips);
+            }
+            Joker::SquareJoker if self.upgrade_condition().evaluate(s) => {
+                let state = s.current_state();
+                state.chips += 4;
+                explain!(s, "🃏{:?} upgraded to +{} Chips", &self.joker, state.chips);
+            }
+            _ => (),
         }
     }
+}
 
-    fn meet_condition(&self, s: &mut Score) -> bool {
-        match &self.joker {
-            Joker::JollyJoker => s.poker_hands.contains(&PokerHand::Pair),
-            Joker::ZanyJoker => s.poker_hands.contains(&PokerHand::ThreeOfAKind),
-            Joker::MadJoker => s.poker_hands.contains(&PokerHand::TwoPair),
-            Joker::CrazyJoker => s.poker_hands.contains(&PokerHand::Straight),
-            Joker::DrollJoker => s.poker_hands.contains(&PokerHand::Flush),
-            Joker::SlyJoker => s.poker_hands.contains(&PokerHand::Pair),
-            Joker::WilyJoker => s.poker_hands.contains(&PokerHand::ThreeOfAKind),
-            Joker::CleverJoker => s.poker_hands.contains(&PokerHand::TwoPair),
-            Joker::DeviousJoker => s.poker_hands.contains(&PokerHand::Straight),
-            Joker::CraftyJoker => s.poker_hands.contains(&PokerHand::Flush),
-            Joker::Blackboard => {
-                let n = num_of_poker_by_suits(
-                    &s.cards_held_in_hand,
-                    vec![Suit::Clubs, Suit::Spades]
-                );
-                return s.cards_held_in_hand.is_empty() || s.cards_held_in_hand.len() == n;
-            }
-            Joker::FlowerPot => {
-                let small: HashSet<Suit> = s.scored_cards
-                    .iter()
-                    .filter(|x| !x.is_wild())
-                    .map(|x| x.suit)
-                    .collect();
-                let big: HashSet<Suit> = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades]
-                    .iter()
-                    .cloned()
-                    .collect();
-                let diff: HashSet<Suit> = big.difference(&small).cloned().collect();
+#[cfg(test)]
+mod tests {
+    use ortalib::PokerHand;
 
-                let n_wild_cards = s.scored_cards
-                    .iter()
-                    .filter(|x| x.is_wild())
-                    .count();
-                return diff.len() <= n_wild_cards;
-            }
-            _ => true,
-        }
+    use crate::{ HandHistory, JokerState, RoundInput, Score };
+
+    fn parse<T: std::str::FromStr<Err = String>>(items: &[&str]) -> Vec<T> {
+        items
+            .iter()
+            .map(|item| item.parse().unwrap())
+            .collect()
     }
-    fn joker_value(&self, s: &Score) -> (Chips, Mult, String) {
-        match self.joker {
-            Joker::Joker => (s.chips, s.mult + 4.0, "+4 Mult".to_string()),
-            Joker::JollyJoker => (s.chips, s.mult + 8.0, "+8 Mult".to_string()),
-            Joker::ZanyJoker => (s.chips, s.mult + 12.0, "+12 Mult".to_string()),
-            Joker::MadJoker => (s.chips, s.mult + 10.0, "+10 Mult".to_string()),
-            Joker::CrazyJoker => (s.chips, s.mult + 12.0, "+12 Mult".to_string()),
-            Joker::DrollJoker => (s.chips, s.mult + 10.0, "+10 Mult".to_string()),
-            Joker::SlyJoker => (s.chips + 50.0, s.mult, "+50 Chips".to_string()),
-            Joker::WilyJoker => (s.chips + 100.0, s.mult, "+100 Chips".to_string()),
-            Joker::CleverJoker => (s.chips + 80.0, s.mult, "+80 Chips".to_string()),
-            Joker::DeviousJoker => (s.chips + 100.0, s.mult, "+100 Chips".to_string()),
-            Joker::CraftyJoker => (s.chips + 80.0, s.mult, "+80 Chips".to_string()),
-            Joker::AbstractJoker => {
-                let n = s.jokers.len() as f64;
-                return (s.chips, s.mult + 3.0 * n, format!("+3x{n} Mult"));
-            }
-            Joker::Blackboard => (s.chips, s.mult * 3.0, "x3 Mult".to_string()),
-            Joker::FlowerPot => (s.chips, s.mult * 3.0, "x3 Mult".to_string()),
-            _ => panic!("Joker type not found"),
+
+    fn round(played: &[&str], held: &[&str], jokers: &[&str]) -> RoundInput {
+        RoundInput {
+            cards_played: parse(played),
+            cards_held_in_hand: parse(held),
+            jokers: parse(jokers),
+            ..RoundInput::default()
         }
     }
+
+    fn scored(round: RoundInput) -> Score {
+        let mut score = Score::new(round);
+        score.calculate_score();
+        score
+    }
+
+    /// Trace messages of the joker phase, without the chips and mult they end on
+    fn joker_phase(score: &Score) -> Vec<&str> {
+        score
+            .trace()
+            .iter()
+            .map(|entry| entry.message.as_str())
+            .skip_while(|message| !message.contains("---jokers----"))
+            .skip(1)
+            .map(|message| message.split(" (").next().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn foil_and_holographic_come_before_the_effect() {
+        let score = scored(round(&["K♥", "K♦"], &[], &["Joker Foil", "Jolly Joker Holographic"]));
+        assert_eq!(joker_phase(&score), [
+            "🃏Joker Foil +50 Chips",
+            "🃏Joker +4 Mult",
+            "🃏Jolly Joker Holographic +10 Mult",
+            "🃏Jolly Joker +8 Mult",
+        ]);
+    }
+
+    #[test]
+    fn polychrome_comes_after_the_effect_and_reactions() {
+        let jokers = ["Blackboard Polychrome", "Baseball Card"];
+        let score = scored(round(&["K♥", "K♦"], &["K♠"], &jokers));
+        assert_eq!(joker_phase(&score), [
+            "🃏Blackboard x3 Mult",
+            "🃏Baseball Card x1.5 Mult for Blackboard",
+            "🃏Blackboard Polychrome x1.5 Mult",
+        ]);
+    }
+
+    #[test]
+    fn on_held_editions_apply_in_the_joker_phase() {
+        let score = scored(round(&["K♥", "K♦"], &["K♠"], &["Baron Holographic"]));
+        assert_eq!(joker_phase(&score), ["🃏Baron Holographic +10 Mult"]);
+        // the held King's x1.5 came before, in the held phase
+        let held = score
+            .trace()
+            .iter()
+            .position(|entry| entry.message.starts_with("🃏 Baron Holographic x1.5 Mult"));
+        let jokers = score
+            .trace()
+            .iter()
+            .position(|entry| entry.message.contains("---jokers----"));
+        assert!(held.unwrap() < jokers.unwrap());
+    }
+
+    #[test]
+    fn untriggered_jokers_keep_their_edition() {
+        let score = scored(round(&["K♥", "K♦"], &[], &["Zany Joker Polychrome"]));
+        assert_eq!(joker_phase(&score), [
+            "🃏Zany Joker not triggered",
+            "🃏Zany Joker Polychrome x1.5 Mult",
+        ]);
+    }
+
+    /// Scores `round`, then the same hand again with the carried over state
+    fn twice(round: RoundInput) -> (Score, Score) {
+        let first = scored(round.clone());
+        let state = first.run_state();
+        let next = RoundInput {
+            money: state.money,
+            joker_state: state.joker_state,
+            hand_history: state.hand_history,
+            ..round
+        };
+        (first, scored(next))
+    }
+
+    #[test]
+    fn ice_cream_melts_between_hands() {
+        let (first, next) = twice(round(&["K♥", "K♦"], &[], &["Ice Cream"]));
+        // Pair (10 + 10 + 10 + 100, 2), then +95 Chips
+        assert_eq!(first.total().to_f64(), 260.0);
+        assert_eq!(first.run_state().joker_state[&0].chips, 95);
+        assert_eq!(next.total().to_f64(), 250.0);
+        assert_eq!(next.run_state().joker_state[&0].chips, 90);
+    }
+
+    #[test]
+    fn ride_the_bus_resets_on_a_scored_face_card() {
+        let mut bus = round(&["2♥", "2♦"], &[], &["Ride the Bus"]);
+        bus.joker_state.insert(0, JokerState { mult: 3, ..JokerState::default() });
+        // no face card, upgraded before scoring: Pair (10 + 2 + 2, 2 + 4)
+        let score = scored(bus.clone());
+        assert_eq!(score.run_state().joker_state[&0].mult, 4);
+        assert_eq!(score.total().to_f64(), 84.0);
+        // a face card scores: reset to +0 Mult, Pair (10 + 10 + 10, 2)
+        bus.cards_played = parse(&["K♥", "K♦"]);
+        let score = scored(bus);
+        assert_eq!(score.run_state().joker_state[&0].mult, 0);
+        assert_eq!(score.total().to_f64(), 60.0);
+    }
+
+    #[test]
+    fn green_joker_grows_every_hand() {
+        let (first, next) = twice(round(&["K♥", "K♦"], &[], &["Green Joker"]));
+        // Pair (10 + 10 + 10, 2 + 1), then 2 + 2
+        assert_eq!(first.total().to_f64(), 90.0);
+        assert_eq!(next.total().to_f64(), 120.0);
+        assert_eq!(next.run_state().joker_state[&0].mult, 2);
+        assert_eq!(next.hand_history().run_plays(PokerHand::Pair), 2);
+    }
+
+    fn with_history(
+        mut round: RoundInput,
+        run: &[(PokerHand, u32)],
+        this_round: &[(PokerHand, u32)]
+    ) -> RoundInput {
+        round.hand_history = HandHistory {
+            run: run.iter().copied().collect(),
+            round: this_round.iter().copied().collect(),
+        };
+        round
+    }
+
+    #[test]
+    fn obelisk_counts_ties_as_not_most_played() {
+        let mut obelisk = round(&["K♥", "K♦"], &[], &["Obelisk"]);
+        obelisk.joker_state.insert(0, JokerState { count: 2, ..JokerState::default() });
+        // with this hand, Pair ties High Card at 2 plays: upgraded to x1.6
+        let history = [(PokerHand::HighCard, 2), (PokerHand::Pair, 1)];
+        let tied = with_history(obelisk.clone(), &history, &[]);
+        let score = scored(tied);
+        assert_eq!(score.run_state().joker_state[&0].count, 3);
+        assert_eq!(score.total().to_f64(), 96.0);
+        // Pair now leads at 2 plays to 1: reset to x1
+        let history = [(PokerHand::HighCard, 1), (PokerHand::Pair, 1)];
+        let ahead = with_history(obelisk, &history, &[]);
+        let score = scored(ahead);
+        assert_eq!(score.run_state().joker_state[&0].count, 0);
+        assert_eq!(score.total().to_f64(), 60.0);
+    }
+
+    #[test]
+    fn supernova_counts_this_hand() {
+        // first Pair of the run: Pair (10 + 10 + 10, 2 + 1)
+        let score = scored(round(&["K♥", "K♦"], &[], &["Supernova"]));
+        assert_eq!(score.total().to_f64(), 90.0);
+        // four Pairs before this one: 2 + 5
+        let supernova = round(&["K♥", "K♦"], &[], &["Supernova"]);
+        let played = with_history(supernova, &[(PokerHand::Pair, 4)], &[]);
+        assert_eq!(scored(played).total().to_f64(), 210.0);
+    }
+
+    #[test]
+    fn card_sharp_counts_this_hand() {
+        // a Pair played once before this round makes this one the second: x3
+        let sharp = round(&["K♥", "K♦"], &[], &["Card Sharp"]);
+        let again = with_history(sharp.clone(), &[(PokerHand::Pair, 1)], &[(PokerHand::Pair, 1)]);
+        assert_eq!(scored(again).total().to_f64(), 180.0);
+        // the first Pair of the round does not trigger it, nor do earlier rounds' Pairs
+        assert_eq!(scored(sharp.clone()).total().to_f64(), 60.0);
+        let earlier = with_history(sharp, &[(PokerHand::Pair, 3)], &[]);
+        assert_eq!(scored(earlier).total().to_f64(), 60.0);
+    }
 }
@@ -1,107 +1,558 @@
 mod card;
+pub use card::{ CardId, Edition, Enhancement, PlayingCard, Seal };
+mod joker;
+pub use joker::{ Joker, JokerCard, JokerState, Rarity };
+mod blind;
+pub use blind::{ Blind, IllegalPlay };
+mod round;
+pub use round::{ HandHistory, RoundContext, RoundInput, RunState, DEFAULT_JOKER_SLOTS };
 
 mod modifiers;
-pub mod config;
-
-pub use config::explain_enabled;
+mod trace;
+mod number;
+pub use number::{ Arithmetic, Number };
+pub use trace::TraceEntry;
+mod report;
+pub use report::ScoreReport;
+#[cfg(target_arch = "wasm32")]
+mod wasm;
+pub mod ffi;
+#[cfg(any(feature = "python", test))]
+mod python;
 mod utils;
+mod condition;
+mod card_trigger;
+pub use card_trigger::{ CardEffect, CardPredicate, OnHeld, OnScored };
+pub use condition::{ Condition, Count };
+pub use utils::DeckStats;
 mod poker_hand;
+pub mod probability;
+use probability::{ Chance, ChanceState, Roll };
 use modifiers::EditionUtils;
 use modifiers::EnhancementUtils;
-use ortalib::Card;
-use ortalib::JokerCard;
+use card::CardUtils;
 
 mod getters;
 pub use getters::EnhancementValueGetter;
-pub use getters::ScoredCardGetter;
 pub use getters::StraghtValueGetter;
 mod jokers;
-pub use jokers::JokerUtils;
-use ortalib::{ Chips, Mult, PokerHand, Round };
+pub use jokers::{ HeldTarget, JokerStep, JokerUtils };
+use ortalib::PokerHand;
 use poker_hand::PokerHandUtils;
+pub use poker_hand::{ classify, parse_hand, HandMatch };
+use blind::BlindUtils;
 
 pub struct Score {
-    pub mult: Mult,
-    pub chips: Chips,
+    pub mult: Number,
+    pub chips: Number,
     best_poker_hand: PokerHand,
-    poker_hands: Vec<PokerHand>,
-    scored_cards: Vec<Card>,
-    // cards_played: Vec<Card>,
-    cards_held_in_hand: Vec<Card>,
+    hand_matches: Vec<HandMatch>,
+    scored_cards: Vec<CardId>,
+    cards_played: Vec<PlayingCard>,
+    cards_held_in_hand: Vec<PlayingCard>,
     jokers: Vec<JokerCard>,
-    raised_first_triggered: bool,
-    photograph_triggered: bool,
+    joker_state: Vec<JokerState>,
+    current_card: Option<CardId>,
+    current_joker: Option<usize>,
+    trace: Vec<TraceEntry>,
+    chance: ChanceState,
+    destroyed_cards: Vec<CardId>,
+    money: i64,
+    starting_money: i64,
+    blind: Option<Blind>,
+    hand_history: HandHistory,
+    context: Option<RoundContext>,
+    deck: Option<DeckStats>,
+    joker_slots: usize,
+    rejection: Option<IllegalPlay>,
 }
 
 impl Score {
-    pub fn new(round: Round) -> Self {
-        let poker_hands = PokerHand::recogonize(&round.cards_played);
-        explain!("{:?}", poker_hands);
-        let best_poker_hand: PokerHand = poker_hands
-            .first()
-            .copied()
-            .unwrap_or(PokerHand::HighCard);
-        let scored_cards = round.get_scored_cards(best_poker_hand);
-        Score {
-            mult: 0.0,
-            chips: 0.0,
-            poker_hands,
-            // cards_played: round.cards_played,
+    /// Takes a `RoundInput` or a plain `ortalib::Round`
+    pub fn new(round: impl Into<RoundInput>) -> Self {
+        Self::with_arithmetic(round, Arithmetic::Float)
+    }
+
+    /// Like `new`, but doing chips and mult arithmetic in the given mode
+    pub fn with_arithmetic(round: impl Into<RoundInput>, arithmetic: Arithmetic) -> Self {
+        let round: RoundInput = round.into();
+        let joker_slots = round.joker_slots();
+        let hand_matches = classify(&round.cards_played);
+        let (best_poker_hand, scored_cards) = match hand_matches.first() {
+            Some(best) => (best.hand, best.cards.clone()),
+            None => (PokerHand::HighCard, vec![]),
+        };
+        let joker_state = round.jokers
+            .iter()
+            .enumerate()
+            .map(|(i, joker)| {
+                round.joker_state.get(&i).cloned().unwrap_or_else(|| joker.initial_state())
+            })
+            .collect();
+        let mut score = Score {
+            mult: Number::zero(arithmetic),
+            chips: Number::zero(arithmetic),
+            hand_matches,
+            cards_played: round.cards_played,
             scored_cards,
             best_poker_hand,
             cards_held_in_hand: round.cards_held_in_hand,
             jokers: round.jokers,
-            photograph_triggered: false,
-            raised_first_triggered: false,
+            joker_state,
+            current_card: None,
+            current_joker: None,
+            trace: Vec::new(),
+            chance: ChanceState::new(Chance::default()),
+            destroyed_cards: Vec::new(),
+            money: round.money,
+            starting_money: round.money,
+            blind: round.blind,
+            hand_history: round.hand_history,
+            context: round.context,
+            deck: round.full_deck.as_deref().map(DeckStats::new),
+            joker_slots,
+            rejection: None,
+        };
+        let hands: Vec<PokerHand> = score.hand_matches
+            .iter()
+            .map(|m| m.hand)
+            .collect();
+        explain!(score, "{:?}", hands);
+        score
+    }
+
+    /// Resolve chance-based effects as `chance` says instead of assuming the worst case
+    pub fn with_chance(mut self, chance: Chance) -> Self {
+        self.chance = ChanceState::new(chance);
+        self
+    }
+
+    /// Roll a `numerator in denominator` chance, scaled by the odds multiplier
+    ///
+    /// `scoring` is false for rolls that can only move money or destroy cards.
+    pub(crate) fn roll(&mut self, numerator: u32, denominator: u32, what: &str, scoring: bool) -> bool {
+        self.chance.roll(numerator, denominator, what, scoring)
+    }
+
+    /// Every chance roll made while calculating the score, in order
+    pub fn rolls(&self) -> &[Roll] {
+        &self.chance.rolls
+    }
+
+    /// Glass cards that broke after scoring
+    pub fn destroyed_cards(&self) -> &[CardId] {
+        &self.destroyed_cards
+    }
+
+    /// Money after the round's money effects
+    pub fn money(&self) -> i64 {
+        self.money
+    }
+
+    /// Money earned (or spent, when negative) during the round
+    pub fn money_delta(&self) -> i64 {
+        self.money - self.starting_money
+    }
+
+    pub(crate) fn earn(&mut self, dollars: i64) {
+        self.money += dollars;
+    }
+
+    /// State of the joker whose effect is being applied
+    pub(crate) fn current_state(&mut self) -> &mut JokerState {
+        let i = self.current_joker.expect("only called while applying a joker");
+        &mut self.joker_state[i]
+    }
+
+    /// Money and joker state to carry over into the next hand
+    pub fn run_state(&self) -> RunState {
+        RunState {
+            money: self.money,
+            joker_state: self.joker_state.iter().cloned().enumerate().collect(),
+            hand_history: self.hand_history.clone(),
         }
     }
+
+    /// Why the blind did not allow this hand, in which case it scored nothing
+    pub fn rejection(&self) -> Option<IllegalPlay> {
+        self.rejection
+    }
+
+    /// Whether the card's abilities are switched off, by its own flag or e.g. The Plant
+    pub fn is_debuffed(&self, id: CardId) -> bool {
+        let card = self.card(id);
+        card.debuffed || self.blind.is_some_and(|blind| blind.debuffs(card))
+    }
+
+    /// Poker hands played so far, including this one once scored
+    pub fn hand_history(&self) -> &HandHistory {
+        &self.hand_history
+    }
+
+    /// Hands, discards and deck around this hand, when the round input has them
+    pub fn context(&self) -> Option<&RoundContext> {
+        self.context.as_ref()
+    }
+
+    /// Composition of the full deck, when the round input lists it
+    pub fn deck(&self) -> Option<&DeckStats> {
+        self.deck.as_ref()
+    }
+
+    /// Joker slots, filled or not, Negative jokers' included
+    pub fn joker_slots(&self) -> usize {
+        self.joker_slots
+    }
+
+    /// The best poker hand recognized in the cards played
+    pub fn best_poker_hand(&self) -> PokerHand {
+        self.best_poker_hand
+    }
+
+    /// Every poker hand contained in the cards played, best first
+    pub fn hand_matches(&self) -> &[HandMatch] {
+        &self.hand_matches
+    }
+
+    /// Whether the cards played contain `hand`, e.g. a Full House contains a Pair
+    pub fn contains_hand(&self, hand: PokerHand) -> bool {
+        self.hand_matches.iter().any(|m| m.hand == hand)
+    }
+
+    /// The card behind `id`
+    pub fn card(&self, id: CardId) -> &PlayingCard {
+        match id {
+            CardId::Played(i) => &self.cards_played[i],
+            CardId::Held(i) => &self.cards_held_in_hand[i],
+        }
+    }
+
+    /// Ids of the cards that score, in the order they were played
+    pub fn scored_cards(&self) -> &[CardId] {
+        &self.scored_cards
+    }
+
+    /// Ids of the held cards a held-in-hand joker with `target` acts on
+    pub fn held_targets(&self, target: HeldTarget) -> Vec<CardId> {
+        let held = (0..self.cards_held_in_hand.len()).map(CardId::Held);
+        match target {
+            HeldTarget::Each => held.collect(),
+            // min_by_key keeps the first minimum, so walk right to left; Stone cards have no rank
+            HeldTarget::LowestRank => held
+                .rev()
+                .filter(|id| !self.card(*id).is_stone())
+                .min_by_key(|id| self.card(*id).rank.straight_value())
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    pub(crate) fn scored(&self) -> impl Iterator<Item = &PlayingCard> {
+        self.scored_cards.iter().map(|id| self.card(*id))
+    }
+
+    /// The final score, i.e. chips times mult rounded down
+    pub fn total(&self) -> Number {
+        (self.chips.clone() * self.mult.clone()).floor()
+    }
+
+    /// Entries recorded while calculating the score, in the order they happened
+    pub fn trace(&self) -> &[TraceEntry] {
+        &self.trace
+    }
+    /// One trigger of a held card: its enhancement, e.g. Steel, then every
+    /// on-held joker targeting it, left to right
+    fn trigger_held(&mut self, id: CardId) {
+        let enhancement = self.card(id).enhancement;
+        enhancement.apply(self, true);
+        let targeting: Vec<JokerCard> = self.jokers
+            .iter()
+            .filter(|e| e.is_on_held())
+            .filter(|e| self.held_targets(e.held_target()).contains(&id))
+            .copied()
+            .collect();
+        targeting.iter().for_each(|x| x.apply_on_held(self, id));
+    }
+
     pub fn calculate_score(&mut self) {
+        if let Some(blind) = self.blind {
+            let played = self.cards_played.len();
+            if let Err(rejection) = blind.check_play(played, self.best_poker_hand, &self.hand_history) {
+                self.rejection = Some(rejection);
+                explain!(self, "{:?} rejects the hand: {}", blind, rejection);
+                return;
+            }
+        }
+        self.hand_history.record(self.best_poker_hand);
         // deal with cards played
-        self.best_poker_hand.apply(&mut self.chips, &mut self.mult);
+        let best_poker_hand = self.best_poker_hand;
+        best_poker_hand.apply(self);
+        // scaling jokers update before any card scores
+        for (i, joker) in self.jokers.clone().iter().enumerate() {
+            self.current_joker = Some(i);
+            joker.update_state(self);
+        }
+        self.current_joker = None;
 
-        explain!("{:?}", self.scored_cards);
+        let scored: Vec<PlayingCard> = self.scored().copied().collect();
+        explain!(self, "{:?}", scored);
         self.scored_cards
             .clone()
             .iter()
-            .for_each(|card| {
-                self.chips += card.rank.rank_value();
-                explain!(
-                    "{:?}{:?} +{:?} chips {:?}",
-                    card.rank,
-                    card.suit,
-                    card.rank.rank_value(),
-                    (self.chips, self.mult)
-                );
-                // modifiers(self.explain, vec![*card], &mut self.chips, &mut self.mult);
-                card.enhancement.apply(self, false);
-                card.edition.apply(self);
-                self.jokers
-                    .clone()
+            .for_each(|&id| {
+                let card = *self.card(id);
+                self.current_card = Some(id);
+                // debuffed cards still count towards the hand but give nothing
+                if self.is_debuffed(id) {
+                    explain!(self, "{:?}{:?} debuffed", card.rank, card.suit);
+                    return;
+                }
+                // jokers like Hack retrigger the card once more each
+                let retriggering: Vec<JokerCard> = self.jokers
                     .iter()
-                    .filter(|e| e.is_on_scored())
-                    .for_each(|x| x.apply_on_scored(self, card));
+                    .filter(|j| !j.debuffed)
+                    .filter(|j| j.on_scored().is_some_and(|o| o.retriggers() && o.predicate.matches(self, id)))
+                    .copied()
+                    .collect();
+                for trigger in 0..card.triggers() + retriggering.len() {
+                    if trigger >= card.triggers() {
+                        explain!(self, "{:?} retriggered by {:?}", card, retriggering[trigger - card.triggers()]);
+                    } else if trigger > 0 {
+                        explain!(self, "{:?} retriggered by Red Seal", card);
+                    }
+                    // Stone cards have no rank, their chips come from the enhancement
+                    if !card.is_stone() {
+                        self.chips += card.rank.rank_value();
+                        explain!(
+                            self,
+                            "{:?}{:?} +{:?} chips {:?}",
+                            card.rank,
+                            card.suit,
+                            card.rank.rank_value(),
+                            (&self.chips, &self.mult)
+                        );
+                    }
+                    // modifiers(self.explain, vec![*card], &mut self.chips, &mut self.mult);
+                    card.enhancement.apply(self, false);
+                    card.edition.apply(self);
+                    if card.seal == Some(Seal::Gold) {
+                        self.earn(3);
+                        explain!(self, "  Gold Seal +$3 (${})", self.money);
+                    }
+                    self.jokers
+                        .clone()
+                        .iter()
+                        .filter(|e| e.is_on_scored())
+                        .for_each(|x| x.apply_on_scored(self, id));
+                }
             });
+        // Glass cards may break once the hand has scored, whatever their retriggers
+        for id in self.scored_cards.clone() {
+            self.current_card = Some(id);
+            let glass = self.card(id).enhancement == Some(Enhancement::Glass);
+            if glass && !self.is_debuffed(id) && self.roll(1, 4, "Glass breaks", false) {
+                self.destroyed_cards.push(id);
+                explain!(self, "{:?} ({}) breaks", *self.card(id), id);
+            }
+        }
         // deal with cards held in hand
-        explain!("\n----cards held in hand----");
+        explain!(self, "\n----cards held in hand----");
         let cards = self.cards_held_in_hand.clone();
-        let len = cards.len();
+        // each Mime retriggers every held card's abilities once more
+        let mimes = self.jokers
+            .iter()
+            .filter(|j| j.joker == Joker::Mime && !j.debuffed)
+            .count();
 
         for (i, card) in cards.into_iter().enumerate() {
-            let is_last = i == len - 1;
-            explain!("☛ {:?}", card);
-            card.enhancement.apply(self, true);
-            self.jokers
-                .clone()
-                .iter()
-                .filter(|e| e.is_on_held())
-                .for_each(|x| x.apply_on_held(self, &card, is_last));
+            let id = CardId::Held(i);
+            self.current_card = Some(id);
+            if self.is_debuffed(id) {
+                explain!(self, "☛ {:?}{:?} debuffed", card.rank, card.suit);
+                continue;
+            }
+            for trigger in 0..card.triggers() + mimes {
+                if trigger == 0 {
+                    explain!(self, "☛ {:?}", card);
+                } else {
+                    explain!(self, "☛ {:?} retriggered", card);
+                }
+                self.trigger_held(id);
+            }
         }
+        self.current_card = None;
         // handle jokers
-        explain!("\n---jokers----");
-        self.jokers
-            .clone()
+        explain!(self, "\n---jokers----");
+        for (i, joker) in self.jokers.clone().iter().enumerate() {
+            self.current_joker = Some(i);
+            joker.apply_joker_phase(self);
+        }
+        self.current_joker = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse<T: std::str::FromStr<Err = String>>(items: &[&str]) -> Vec<T> {
+        items
+            .iter()
+            .map(|item| item.parse().unwrap())
+            .collect()
+    }
+
+    fn round(played: &[&str], held: &[&str], jokers: &[&str]) -> RoundInput {
+        RoundInput {
+            cards_played: parse(played),
+            cards_held_in_hand: parse(held),
+            jokers: parse(jokers),
+            ..RoundInput::default()
+        }
+    }
+
+    fn scored(round: &RoundInput, arithmetic: Arithmetic) -> Score {
+        let mut score = Score::with_arithmetic(round.clone(), arithmetic);
+        score.calculate_score();
+        score
+    }
+
+    fn total(played: &[&str], held: &[&str], jokers: &[&str]) -> f64 {
+        scored(&round(played, held, jokers), Arithmetic::Float).total().to_f64()
+    }
+
+    #[test]
+    fn photograph_doubles_the_first_face_card_only() {
+        // Pair (10, 2): K♥ +10, x2 Mult, K♠ +10
+        assert_eq!(total(&["K♥", "K♠"], &[], &["Photograph"]), 120.0);
+    }
+
+    #[test]
+    fn photograph_applies_when_the_face_card_scores() {
+        // Two Pair (20, 2), cards in play order: the Mult card's +4 before the x2
+        assert_eq!(total(&["5♠ Mult", "K♥", "5♦", "K♣"], &[], &["Photograph"]), 600.0);
+        // face card played first: x2 before the +4
+        assert_eq!(total(&["K♥", "5♠ Mult", "K♣", "5♦"], &[], &["Photograph"]), 400.0);
+    }
+
+    #[test]
+    fn photograph_skips_stone_cards() {
+        // Pair (10, 2): K♠ Stone +50 is not a face card, Q♥ +10 gets the x2, Q♦ +10
+        assert_eq!(total(&["K♠ Stone", "Q♥", "Q♦"], &[], &["Photograph"]), 320.0);
+    }
+
+    #[test]
+    fn raised_fist_targets_the_lowest_rank() {
+        let round = round(&["K♥", "K♦"], &["K♠", "5♥", "9♦"], &["Raised Fist"]);
+        let score = scored(&round, Arithmetic::Float);
+        assert_eq!(score.held_targets(HeldTarget::LowestRank), [CardId::Held(1)]);
+        // Pair (10 + 10 + 10, 2 + 2x5)
+        assert_eq!(score.total().to_f64(), 360.0);
+    }
+
+    #[test]
+    fn raised_fist_targets_the_rightmost_on_ties() {
+        let round = round(&["K♥", "K♦"], &["5♠", "9♦", "5♥"], &["Raised Fist"]);
+        let score = scored(&round, Arithmetic::Float);
+        assert_eq!(score.held_targets(HeldTarget::LowestRank), [CardId::Held(2)]);
+        let fist = score
+            .trace()
             .iter()
-            .filter(|e| e.is_dependent())
-            .for_each(|x| x.apply_independent(self));
+            .find(|entry| entry.message.starts_with("🃏 Raised Fist"))
+            .unwrap();
+        assert_eq!(fist.card, Some(CardId::Held(2)));
+    }
+
+    #[test]
+    fn mime_retriggers_the_raised_fist_target() {
+        // Pair (10 + 10 + 10, 2 + 2x5 + 2x5)
+        assert_eq!(total(&["K♥", "K♦"], &["9♦", "5♥"], &["Raised Fist", "Mime"]), 660.0);
+    }
+
+    #[test]
+    fn raised_fist_skips_stone_cards() {
+        // Pair (10 + 10 + 10, 2), Raised Fist +2x5 on 5♥, not the Stone card's hidden 2
+        assert_eq!(total(&["K♥", "K♠"], &["2♠ Stone", "5♥"], &["Raised Fist"]), 360.0);
+        // only Stone cards held, nothing to target
+        assert_eq!(total(&["K♥", "K♠"], &["2♠ Stone"], &["Raised Fist"]), 60.0);
+    }
+
+    #[test]
+    fn flower_pot_ignores_stone_suits() {
+        // Three of a Kind (30 + 2 + 2 + 2 + 50, 3): the Stone card's ♠ does not complete the suits
+        assert_eq!(total(&["2♥", "2♦", "2♣", "2♠ Stone"], &[], &["Flower Pot"]), 258.0);
+        // Four of a Kind (60 + 8, 7) with a real ♠ does, x3
+        assert_eq!(total(&["2♥", "2♦", "2♣", "2♠"], &[], &["Flower Pot"]), 1428.0);
+    }
+
+    #[test]
+    fn debuffed_cards_count_towards_the_hand_only() {
+        let round = round(&["K♥ Mult Foil Debuffed", "K♦"], &[], &["Lusty Joker"]);
+        let score = scored(&round, Arithmetic::Float);
+        assert_eq!(score.best_poker_hand(), PokerHand::Pair);
+        assert_eq!(score.scored_cards(), [CardId::Played(0), CardId::Played(1)]);
+        // Pair (10 + 10, 2): no K♥ chips, Mult, Foil or Lusty Joker
+        assert_eq!(score.total().to_f64(), 40.0);
+        assert_eq!(total(&["K♥ Mult Foil", "K♦"], &[], &["Lusty Joker"]), 720.0);
+    }
+
+    #[test]
+    fn debuffed_retriggers_do_nothing() {
+        // Pair (10 + 10 + 10, 2), K♠ Steel x1.5 once
+        assert_eq!(total(&["K♥", "K♦"], &["K♠ Steel"], &["Mime Debuffed"]), 90.0);
+        assert_eq!(total(&["K♥", "K♦"], &["K♠ Steel"], &["Mime"]), 135.0);
+        // Pair (10 + 2 + 2, 2)
+        assert_eq!(total(&["2♥", "2♦"], &[], &["Hack Debuffed"]), 28.0);
+        assert_eq!(total(&["2♥", "2♦"], &[], &["Hack"]), 36.0);
+    }
+
+    #[test]
+    fn debuffed_jokers_do_nothing() {
+        // Pair (10 + 10 + 10, 2), no +4 Mult and no Polychrome
+        assert_eq!(total(&["K♥", "K♦"], &[], &["Joker Polychrome Debuffed"]), 60.0);
+        // no x1.5 for the held King and no Holographic
+        assert_eq!(total(&["K♥", "K♦"], &["K♠"], &["Baron Holographic Debuffed"]), 60.0);
+        // Rough Gem earns nothing for the Diamond
+        let round = round(&["K♥", "K♦"], &[], &["Rough Gem Debuffed"]);
+        assert_eq!(scored(&round, Arithmetic::Float).money_delta(), 0);
+    }
+
+    #[test]
+    fn exact_matches_float_where_float_is_exact() {
+        let rounds = [
+            round(&["K♥", "K♦"], &[], &["Joker"]),
+            round(&["A♠ Glass Polychrome", "A♣ Bonus Foil", "A♥ Mult Holographic"], &["K♠ Steel"], &[
+                "Baron Polychrome",
+                "Mime",
+            ]),
+            round(&["2♥", "3♥", "4♥", "5♥", "6♥"], &["Q♣"], &[
+                "Lusty Joker Foil",
+                "Raised Fist",
+                "Blackboard Holographic",
+                "Abstract Joker",
+            ]),
+            round(&["10♠ Stone", "10♣", "10♦ Wild"], &[], &["Steel Joker", "Stuntman Polychrome"]),
+        ];
+        for round in &rounds {
+            let float = scored(round, Arithmetic::Float);
+            let exact = scored(round, Arithmetic::Exact);
+            assert_eq!(exact.total().arithmetic(), Arithmetic::Exact);
+            assert_eq!(float.total().to_string(), exact.total().to_string(), "{round:?}");
+            assert_eq!(float.chips.to_f64(), exact.chips.to_f64(), "{round:?}");
+            assert_eq!(float.mult.to_f64(), exact.mult.to_f64(), "{round:?}");
+        }
+    }
+
+    #[test]
+    fn exact_report_keeps_digits_float_loses() {
+        let held = ["K♠ Steel"; 16];
+        let round = round(&["A♠ Glass Polychrome", "A♣ Glass Polychrome", "A♥ Glass Polychrome"], &held, &[
+            "Baron Polychrome",
+            "Mime Polychrome",
+        ]);
+        let float = scored(&round, Arithmetic::Float).report(false);
+        let exact = scored(&round, Arithmetic::Exact).report(false);
+        assert_eq!(exact.exact_score, "2137217226308893");
+        assert_eq!(float.exact_score, "2137217226308894");
     }
 }
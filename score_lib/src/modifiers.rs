@@ -1,28 +1,23 @@
-use ortalib::{ Edition, Enhancement };
-
-use crate::{ explain, getters::EditionValueGetter, EnhancementValueGetter, Score };
+use crate::{ card::{ Edition, Enhancement }, explain, getters::EditionValueGetter, EnhancementValueGetter, Score };
 
 /// Trait for applying Edition effects to Score
 pub trait EditionUtils {
     /// Apply edition effects to score
-    fn apply(&self, s: &mut Score) -> ();
+    fn apply(&self, s: &mut Score);
 }
 
 impl EditionUtils for Option<Edition> {
     /// Apply edition effects if present
-    fn apply(&self, s: &mut Score) -> () {
-        match self {
-            Some(e) => {
-                // Get and apply edition values
-                let (c, m, msg) = e.eidtion_value(s.mult);
-                s.chips += c;
-                s.mult += m;
-                if msg == "" {
-                    return;
-                }
-                explain!("  {:?} {} {:?}", e, msg, (s.chips, s.mult));
+    fn apply(&self, s: &mut Score) {
+        if let Some(e) = self {
+            // Get and apply edition values
+            let (c, m, msg) = e.eidtion_value(&s.mult);
+            s.chips += c;
+            s.mult += m;
+            if msg.is_empty() {
+                return;
             }
-            _ => (),
+            explain!(s, "  {:?} {} {:?}", e, msg, (&s.chips, &s.mult));
         }
     }
 }
@@ -30,22 +25,40 @@ impl EditionUtils for Option<Edition> {
 /// Trait for applying Enhancement effects to Score
 pub trait EnhancementUtils {
     /// Apply enhancement effects to score
-    fn apply(&self, s: &mut Score, is_held_in_hand: bool) -> ();
+    fn apply(&self, s: &mut Score, is_held_in_hand: bool);
 }
 
 impl EnhancementUtils for Option<Enhancement> {
     /// Apply enhancement effects if present
-    fn apply(&self, s: &mut Score, is_held_in_hand: bool) -> () {
+    fn apply(&self, s: &mut Score, is_held_in_hand: bool) {
         match self {
+            Some(Enhancement::Lucky) if !is_held_in_hand => {
+                if s.roll(1, 5, "Lucky +20 Mult", true) {
+                    s.mult += 20.0;
+                    explain!(s, "  Lucky +20 Mult {:?}", (&s.chips, &s.mult));
+                } else {
+                    explain!(s, "  Lucky +20 Mult not triggered");
+                }
+                if s.roll(1, 15, "Lucky +$20", false) {
+                    s.earn(20);
+                    explain!(s, "  Lucky +$20 (${})", s.money());
+                } else {
+                    explain!(s, "  Lucky +$20 not triggered");
+                }
+            }
+            Some(Enhancement::Gold) if is_held_in_hand => {
+                s.earn(3);
+                explain!(s, "  Gold +$3 (${})", s.money());
+            }
             Some(e) => {
                 // Get and apply enhancement values
-                let (c, m, msg) = e.enhancement_value(s.mult, is_held_in_hand);
-                if msg == "" {
+                let (c, m, msg) = e.enhancement_value(&s.mult, is_held_in_hand);
+                if msg.is_empty() {
                     return;
                 }
                 s.chips += c;
                 s.mult += m;
-                explain!("  {:?} {} {:?}", e, msg, (s.chips, s.mult))
+                explain!(s, "  {:?} {} {:?}", e, msg, (&s.chips, &s.mult))
             }
             _ => (),
         }
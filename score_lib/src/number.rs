@@ -0,0 +1,222 @@
+use std::{ fmt, ops::{ Add, AddAssign, Div, Mul, MulAssign } };
+
+use num_rational::BigRational;
+use num_traits::{ ToPrimitive, Zero };
+
+/// How a `Score` does its chips and mult arithmetic
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Arithmetic {
+    /// Plain `f64`, as `ortalib::Chips` and `ortalib::Mult`
+    #[default]
+    Float,
+    /// Arbitrary precision rationals, immune to rounding on long xMult chains
+    Exact,
+}
+
+/// A chips or mult value in either arithmetic mode
+///
+/// Mixing the two promotes to `Exact`; operations against a plain `f64` keep
+/// the mode of the `Number`. Every constant the game uses is an integer or a
+/// half, so converting it to a rational is lossless.
+#[derive(Clone, PartialEq)]
+pub enum Number {
+    Float(f64),
+    Exact(BigRational),
+}
+
+fn exact(value: f64) -> BigRational {
+    BigRational::from_float(value).expect("chips and mult are always finite")
+}
+
+impl Number {
+    pub fn zero(arithmetic: Arithmetic) -> Self {
+        match arithmetic {
+            Arithmetic::Float => Number::Float(0.0),
+            Arithmetic::Exact => Number::Exact(BigRational::zero()),
+        }
+    }
+
+    pub fn arithmetic(&self) -> Arithmetic {
+        match self {
+            Number::Float(_) => Arithmetic::Float,
+            Number::Exact(_) => Arithmetic::Exact,
+        }
+    }
+
+    /// Nearest `f64`, lossy for huge exact values
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            Number::Float(v) => *v,
+            Number::Exact(v) => v.to_f64().unwrap_or(f64::INFINITY),
+        }
+    }
+
+    pub fn floor(&self) -> Number {
+        match self {
+            Number::Float(v) => Number::Float(v.floor()),
+            Number::Exact(v) => Number::Exact(v.floor()),
+        }
+    }
+
+    /// Promote a pair of numbers to a common mode
+    fn unify(self, other: Number) -> (Number, Number) {
+        match (self, other) {
+            (Number::Float(a), Number::Exact(b)) => (Number::Exact(exact(a)), Number::Exact(b)),
+            (Number::Exact(a), Number::Float(b)) => (Number::Exact(a), Number::Exact(exact(b))),
+            pair => pair,
+        }
+    }
+
+    /// Same mode as `self`, holding `value`
+    fn like(&self, value: f64) -> Number {
+        match self {
+            Number::Float(_) => Number::Float(value),
+            Number::Exact(_) => Number::Exact(exact(value)),
+        }
+    }
+}
+
+impl From<f64> for Number {
+    fn from(value: f64) -> Self {
+        Number::Float(value)
+    }
+}
+
+impl Add for Number {
+    type Output = Number;
+    fn add(self, rhs: Number) -> Number {
+        match self.unify(rhs) {
+            (Number::Float(a), Number::Float(b)) => Number::Float(a + b),
+            (Number::Exact(a), Number::Exact(b)) => Number::Exact(a + b),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Mul for Number {
+    type Output = Number;
+    fn mul(self, rhs: Number) -> Number {
+        match self.unify(rhs) {
+            (Number::Float(a), Number::Float(b)) => Number::Float(a * b),
+            (Number::Exact(a), Number::Exact(b)) => Number::Exact(a * b),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Div for Number {
+    type Output = Number;
+    fn div(self, rhs: Number) -> Number {
+        match self.unify(rhs) {
+            (Number::Float(a), Number::Float(b)) => Number::Float(a / b),
+            (Number::Exact(a), Number::Exact(b)) => Number::Exact(a / b),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Add<f64> for Number {
+    type Output = Number;
+    fn add(self, rhs: f64) -> Number {
+        let rhs = self.like(rhs);
+        self + rhs
+    }
+}
+
+impl Mul<f64> for Number {
+    type Output = Number;
+    fn mul(self, rhs: f64) -> Number {
+        let rhs = self.like(rhs);
+        self * rhs
+    }
+}
+
+impl Div<f64> for Number {
+    type Output = Number;
+    fn div(self, rhs: f64) -> Number {
+        let rhs = self.like(rhs);
+        self / rhs
+    }
+}
+
+impl AddAssign for Number {
+    fn add_assign(&mut self, rhs: Number) {
+        *self = std::mem::replace(self, Number::Float(0.0)) + rhs;
+    }
+}
+
+impl AddAssign<f64> for Number {
+    fn add_assign(&mut self, rhs: f64) {
+        *self = std::mem::replace(self, Number::Float(0.0)) + rhs;
+    }
+}
+
+impl MulAssign<f64> for Number {
+    fn mul_assign(&mut self, rhs: f64) {
+        *self = std::mem::replace(self, Number::Float(0.0)) * rhs;
+    }
+}
+
+impl fmt::Debug for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Float(v) => fmt::Debug::fmt(v, f),
+            Number::Exact(v) => fmt::Display::fmt(v, f),
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Float(v) => fmt::Display::fmt(v, f),
+            Number::Exact(v) => fmt::Display::fmt(v, f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn both(value: f64) -> (Number, Number) {
+        (Number::Float(value), Number::zero(Arithmetic::Exact) + value)
+    }
+
+    #[test]
+    fn exact_matches_float_on_game_values() {
+        // the sums and products of integers and halves that scoring produces
+        let (mut float, mut exact) = both(30.0);
+        for (add, times) in [(50.0, 1.5), (10.0, 2.0), (4.0, 1.5), (0.5, 3.0), (20.0, 1.5)] {
+            float = (float + add) * times;
+            exact = (exact + add) * times;
+        }
+        assert_eq!(exact.arithmetic(), Arithmetic::Exact);
+        assert_eq!(float.to_f64(), exact.to_f64());
+        assert_eq!(float.floor().to_string(), exact.floor().to_string());
+    }
+
+    #[test]
+    fn exact_division_stays_rational() {
+        let (float, exact) = both(3.0);
+        assert_eq!((exact.clone() / 5.0).to_string(), "3/5");
+        assert_eq!(((exact / 5.0) * 5.0).to_string(), "3");
+        assert_eq!((float / 5.0).to_f64(), 0.6);
+    }
+
+    #[test]
+    fn mixing_modes_promotes_to_exact() {
+        let mixed = Number::Float(2.0) * (Number::zero(Arithmetic::Exact) + 1.5);
+        assert_eq!(mixed.arithmetic(), Arithmetic::Exact);
+        assert_eq!(mixed.to_string(), "3");
+    }
+
+    #[test]
+    fn exact_keeps_digits_float_rounds_away() {
+        // 2^53, past which f64 no longer has every integer
+        let (float, exact) = both(9007199254740992.0);
+        let (float, exact) = (float + 1.0, exact + 1.0);
+        assert_eq!(float.to_string(), "9007199254740992");
+        assert_eq!(exact.to_string(), "9007199254740993");
+    }
+}
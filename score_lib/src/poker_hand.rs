@@ -1,187 +1,267 @@
 use crate::{
+    blind::BlindUtils,
+    card::{ CardId, CardUtils, PlayingCard },
     explain,
     getters::StraghtValueGetter,
-    utils::{ count_poker_by_rank, max_num_of_rank },
+    utils::group_poker_by_rank,
+    Score,
 };
 
-use ortalib::{ Card, Chips, Enhancement, Mult, PokerHand, Rank, Suit };
-use std::{ collections::{ HashSet }, vec };
-
-/// Checks if all cards in the played hand are of the same suit
-/// Returns true if all non-wild cards are of the same suit or if all cards are wild
-fn same_suite(cards_played: &Vec<Card>) -> bool {
-    let mut suit_set: HashSet<Suit> = HashSet::new();
-    for card in cards_played {
-        match card.enhancement {
-            // Ignore wild cards when checking for flush
-            Some(Enhancement::Wild) => (),
-            _ => {
-                suit_set.insert(card.suit);
-            }
-        }
-    }
-    // check if we have less than 1 suit (can be 0 if all cards are wild)
-    suit_set.len() < 1
+use ortalib::{ PokerHand, Rank, Suit };
+
+/// A poker hand contained in the cards played, together with the cards that make it up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandMatch {
+    pub hand: PokerHand,
+    /// Cards participating in the hand, in the order they were played
+    pub cards: Vec<CardId>,
 }
 
-/// Trait defining utility methods for poker hand evaluation
-pub trait PokerHandUtils {
-    /// Checks if the given cards match this poker hand
-    fn is(&self, cards_played: &Vec<Card>) -> bool;
+/// Every poker hand, worst first
+const HANDS: [PokerHand; 12] = [
+    PokerHand::HighCard,
+    PokerHand::Pair,
+    PokerHand::TwoPair,
+    PokerHand::ThreeOfAKind,
+    PokerHand::Straight,
+    PokerHand::Flush,
+    PokerHand::FullHouse,
+    PokerHand::FourOfAKind,
+    PokerHand::StraightFlush,
+    PokerHand::FiveOfAKind,
+    PokerHand::FlushHouse,
+    PokerHand::FlushFive,
+];
+
+/// Parse a poker hand from the name it displays as, e.g. `Two Pair`
+pub fn parse_hand(s: &str) -> Result<PokerHand, String> {
+    HANDS.iter()
+        .find(|hand| hand.to_string() == s)
+        .copied()
+        .ok_or_else(|| format!("Invalid PokerHand: `{s}`"))
+}
+
+/// Serde helpers for per-hand counts keyed by hand name in round files
+pub(crate) mod hand_counts {
+    use ortalib::PokerHand;
+    use serde::{ de, Deserialize, Deserializer, Serializer };
+    use std::collections::BTreeMap;
+
+    pub fn serialize<S: Serializer>(
+        counts: &BTreeMap<PokerHand, u32>,
+        serializer: S
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(counts.iter().map(|(hand, n)| (hand.to_string(), n)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<BTreeMap<PokerHand, u32>, D::Error> {
+        BTreeMap::<String, u32>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(name, n)| Ok((super::parse_hand(&name).map_err(de::Error::custom)?, n)))
+            .collect()
+    }
+}
+
+/// Checks if all cards in the played hand can be read as one suit
+/// Wild cards count as every suit, so a hand of only wild cards is a flush too
+fn is_flush(cards: &[PlayingCard]) -> bool {
+    if cards.len() < 5 {
+        return false;
+    }
+    [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds]
+        .iter()
+        .any(|suit| cards.iter().all(|card| card.suit == *suit || card.is_wild()))
+}
+
+/// Checks if the played hand is five consecutive ranks, Ace high or low
+fn is_straight(cards: &[PlayingCard]) -> bool {
+    if cards.len() < 5 {
+        return false;
+    }
 
-    /// Recognizes all possible poker hands from the given cards
-    fn recogonize(cards_played: &Vec<Card>) -> Vec<PokerHand>;
+    let mut cards = cards.to_vec();
+    cards.sort_by_key(|card| card.rank.straight_value());
+
+    // Check if cards are in sequential order
+    let is_consecutive = cards
+        .windows(2)
+        .all(|w| w[1].rank.straight_value() == w[0].rank.straight_value() + 1);
+    if is_consecutive {
+        return true;
+    }
+
+    // Special case for A-2-3-4-5 straight
+    [Rank::Ace, Rank::Two, Rank::Three, Rank::Four, Rank::Five]
+        .iter()
+        .all(|rank| cards.iter().any(|card| card.rank == *rank))
+}
+
+/// Classifies the cards played in a single pass over their rank groups
+///
+/// Returns every poker hand contained in `cards_played`, best first. Any non-empty
+/// hand contains High Card, so the first entry is the hand that gets scored.
+/// Stone cards never help form a hand, but score along with every hand.
+pub fn classify(cards_played: &[PlayingCard]) -> Vec<HandMatch> {
+    let rank_of = |i: &usize| cards_played[*i].rank.straight_value();
+    // largest groups first, higher ranks first among groups of the same size
+    let mut groups: Vec<Vec<usize>> = group_poker_by_rank(cards_played).into_values().collect();
+    groups.sort_by(|a, b| b.len().cmp(&a.len()).then(rank_of(&b[0]).cmp(&rank_of(&a[0]))));
+
+    let ranked: Vec<usize> = groups.iter().flatten().copied().collect();
+    let ranked_cards: Vec<PlayingCard> = ranked
+        .iter()
+        .map(|i| cards_played[*i])
+        .collect();
+    let stones: Vec<usize> = (0..cards_played.len()).filter(|i| !ranked.contains(i)).collect();
+
+    let largest = groups.first().map_or(0, |group| group.len());
+    let pairs: Vec<&[usize]> = groups
+        .iter()
+        .filter(|group| group.len() >= 2)
+        .map(|group| &group[..2])
+        .collect();
+    let full_house = largest >= 3 && groups.iter().skip(1).any(|group| group.len() >= 2);
+    let flush = is_flush(&ranked_cards);
+    let straight = is_straight(&ranked_cards);
+
+    let mut matches = Vec::new();
+    let mut push = |hand: PokerHand, chosen: &[usize]| {
+        // ids in play order, so duplicates like two K♥ stay distinct
+        let mut cards: Vec<usize> = chosen.iter().chain(&stones).copied().collect();
+        cards.sort();
+        matches.push(HandMatch { hand, cards: cards.into_iter().map(CardId::Played).collect() });
+    };
+
+    if largest == 5 && flush {
+        push(PokerHand::FlushFive, &ranked);
+    }
+    if full_house && flush {
+        push(PokerHand::FlushHouse, &ranked);
+    }
+    if largest == 5 {
+        push(PokerHand::FiveOfAKind, &ranked);
+    }
+    if straight && flush {
+        push(PokerHand::StraightFlush, &ranked);
+    }
+    if largest >= 4 {
+        push(PokerHand::FourOfAKind, &groups[0][..4]);
+    }
+    if full_house {
+        push(PokerHand::FullHouse, &ranked);
+    }
+    if flush {
+        push(PokerHand::Flush, &ranked);
+    }
+    if straight {
+        push(PokerHand::Straight, &ranked);
+    }
+    if largest >= 3 {
+        push(PokerHand::ThreeOfAKind, &groups[0][..3]);
+    }
+    if pairs.len() >= 2 {
+        push(PokerHand::TwoPair, &[pairs[0], pairs[1]].concat());
+    }
+    if let Some(pair) = pairs.first() {
+        push(PokerHand::Pair, pair);
+    }
+    if !cards_played.is_empty() {
+        let highest: Option<usize> = ranked.iter().copied().max_by_key(rank_of);
+        push(PokerHand::HighCard, &highest.into_iter().collect::<Vec<_>>());
+    }
 
-    /// Returns a vector of all possible poker hands in descending order of value
-    fn vector() -> Vec<PokerHand>;
+    matches
+}
 
-    /// Applies the poker hand's value to the chips and multiplier
-    fn apply(&self, chips: &mut Chips, mult: &mut Mult) -> ();
+/// Trait defining utility methods for poker hands
+pub trait PokerHandUtils {
+    /// Applies the poker hand's value to the score's chips and multiplier
+    fn apply(&self, s: &mut Score);
 }
 
 impl PokerHandUtils for PokerHand {
     /// Apply the hand's value to chips and multiplier
-    fn apply(&self, chips: &mut Chips, mult: &mut Mult) -> () {
+    fn apply(&self, s: &mut Score) {
         let (c, m) = self.hand_value();
-        // Add the values to the provided chips and multiplier
-        *chips += c;
-        *mult += m;
-        explain!("{:?} {:?}", self, (chips, mult));
-    }
-
-    /// Recognize all poker hands that match the given cards
-    fn recogonize(cards_played: &Vec<Card>) -> Vec<PokerHand> {
-        // Get all possible poker hands in order
-        let vs = Self::vector();
-        let mut matches = Vec::new();
-        // Check each hand type
-        for v in vs {
-            if v.is(&cards_played) {
-                matches.push(v);
-            }
-        }
-        matches
-    }
-
-    /// Determine if the given cards match this poker hand
-    fn is(&self, cards_played: &Vec<Card>) -> bool {
-        match *self {
-            PokerHand::FlushFive =>
-                // suite set might be 0 when 5 cards are all wild
-                PokerHand::FiveOfAKind.is(cards_played) && same_suite(cards_played),
-
-            PokerHand::FlushHouse =>
-                PokerHand::FullHouse.is(cards_played) && same_suite(cards_played),
-
-            PokerHand::FiveOfAKind => max_num_of_rank(cards_played) == 5,
-
-            PokerHand::StraightFlush =>
-                PokerHand::Flush.is(cards_played) && PokerHand::Straight.is(cards_played),
-
-            PokerHand::FourOfAKind => max_num_of_rank(cards_played) >= 4,
-
-            PokerHand::FullHouse =>
-                PokerHand::ThreeOfAKind.is(cards_played) && PokerHand::TwoPair.is(cards_played),
-
-            PokerHand::Flush => {
-                if cards_played.len() < 5 {
-                    return false;
-                }
-                let suit = cards_played[0].suit;
-
-                for card in cards_played {
-                    let is_wild = match card.enhancement {
-                        Some(e) => e == Enhancement::Wild,
-                        None => false,
-                    };
-
-                    if card.suit == suit || is_wild {
-                        continue;
-                    } else {
-                        return false;
-                    }
-                }
-                return true;
-            }
-
-            PokerHand::Straight => {
-                if cards_played.len() < 5 {
-                    return false;
-                }
-
-                let mut cards = cards_played.clone();
-                cards.sort_by_key(|card| card.rank.straight_value());
-
-                // Check if cards are in sequential order
-                let mut is_consecutive = true;
-                for i in 0..cards.len() - 1 {
-                    if cards[i + 1].rank.straight_value() != cards[i].rank.straight_value() + 1 {
-                        is_consecutive = false;
-                        break;
-                    }
-                }
-
-                if is_consecutive {
-                    return true;
-                }
-
-                // Special case for A-2-3-4-5 straight
-                let has_ace = cards.iter().any(|card| matches!(card.rank, Rank::Ace));
-                let has_two = cards.iter().any(|card| matches!(card.rank, Rank::Two));
-                let has_three = cards.iter().any(|card| matches!(card.rank, Rank::Three));
-                let has_four = cards.iter().any(|card| matches!(card.rank, Rank::Four));
-                let has_five = cards.iter().any(|card| matches!(card.rank, Rank::Five));
-
-                has_ace && has_two && has_three && has_four && has_five
-            }
-
-            PokerHand::ThreeOfAKind => max_num_of_rank(cards_played) >= 3,
-
-            PokerHand::TwoPair => {
-                let m = count_poker_by_rank(cards_played);
-                let mut k = 0;
-                for (_, count) in m {
-                    if count >= 2 {
-                        k += 1;
-                    }
-                    if k == 2 {
-                        return true;
-                    }
-                }
-                return false;
-            }
-
-            PokerHand::Pair => {
-                let m = count_poker_by_rank(cards_played);
-                for (_, count) in m {
-                    // higher hands like five of a kind,
-                    // four of a kind, three of a kind can also be considered as pair
-                    if count >= 2 {
-                        return true;
-                    }
-                }
-                return false;
-            }
-
-            PokerHand::HighCard => max_num_of_rank(cards_played) == 1,
-        }
-    }
-
-    /// Return vector of all poker hands in descending order of value
-    fn vector() -> Vec<PokerHand> {
-        return vec![
-            PokerHand::FlushFive,
+        let (c, m) = match s.blind {
+            Some(blind) => blind.hand_value(c, m),
+            None => (c, m),
+        };
+        // Add the values to the score's chips and multiplier
+        s.chips += c;
+        s.mult += m;
+        explain!(s, "{:?} {:?}", self, (&s.chips, &s.mult));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn played(cards: &[&str]) -> Vec<PlayingCard> {
+        cards
+            .iter()
+            .map(|card| card.parse().unwrap())
+            .collect()
+    }
+
+    fn best(cards: &[&str]) -> HandMatch {
+        classify(&played(cards)).remove(0)
+    }
+
+    #[test]
+    fn scored_cards_keep_play_order() {
+        let pair = best(&["7♥", "K♥", "2♠", "K♣"]);
+        assert_eq!(pair.hand, PokerHand::Pair);
+        assert_eq!(pair.cards, vec![CardId::Played(1), CardId::Played(3)]);
+
+        // the low pair was played first, the high pair does not move ahead of it
+        let two_pair = best(&["2♠", "K♥", "7♥", "2♦", "K♣"]);
+        assert_eq!(two_pair.hand, PokerHand::TwoPair);
+        assert_eq!(two_pair.cards, [0, 1, 3, 4].map(CardId::Played).to_vec());
+
+        let full_house = best(&["3♠", "K♥", "3♦", "K♣", "3♥"]);
+        assert_eq!(full_house.hand, PokerHand::FullHouse);
+        assert_eq!(full_house.cards, (0..5).map(CardId::Played).collect::<Vec<_>>());
+    }
+
+    fn hands(cards: &[&str]) -> Vec<PokerHand> {
+        classify(&played(cards)).into_iter().map(|m| m.hand).collect()
+    }
+
+    #[test]
+    fn flush_house_needs_no_wild_cards() {
+        assert_eq!(hands(&["K♥", "K♥", "K♥", "Q♥", "Q♥"]), vec![
             PokerHand::FlushHouse,
-            PokerHand::FiveOfAKind,
-            PokerHand::StraightFlush,
-            PokerHand::FourOfAKind,
             PokerHand::FullHouse,
             PokerHand::Flush,
-            PokerHand::Straight,
             PokerHand::ThreeOfAKind,
             PokerHand::TwoPair,
             PokerHand::Pair,
-            PokerHand::HighCard
-        ];
+            PokerHand::HighCard,
+        ]);
+    }
+
+    #[test]
+    fn flush_five_needs_no_wild_cards() {
+        let flush_five = best(&["7♠", "7♠", "7♠", "7♠", "7♠"]);
+        assert_eq!(flush_five.hand, PokerHand::FlushFive);
+        assert_eq!(flush_five.cards, (0..5).map(CardId::Played).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn stone_cards_break_a_flush() {
+        // the Stone card has no suit, leaving four hearts
+        let hands = hands(&["2♥", "5♥", "8♥", "J♥", "K♥ Stone"]);
+        assert_eq!(hands, vec![PokerHand::HighCard]);
+    }
+
+    #[test]
+    fn stone_cards_score_in_play_order() {
+        let pair = best(&["A♠ Stone", "9♥", "9♦"]);
+        assert_eq!(pair.hand, PokerHand::Pair);
+        assert_eq!(pair.cards, [0, 1, 2].map(CardId::Played).to_vec());
     }
 }
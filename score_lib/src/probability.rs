@@ -0,0 +1,413 @@
+use serde::Serialize;
+
+use crate::{ Arithmetic, Number, RoundInput, Score };
+
+/// How chance-based effects (Lucky cards, Glass cards breaking, ...) are resolved
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChanceMode {
+    /// Roll a seeded RNG, the same seed always gives the same outcome
+    Sample { seed: u64 },
+    /// Every chance effect fails, the guaranteed minimum
+    WorstCase,
+    /// Follow a fixed list of outcomes, failing every roll past its end
+    ///
+    /// `outcomes` uses this to walk every way a round's score can play out.
+    Replay(Vec<bool>),
+}
+
+/// Settings for chance-based effects
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Chance {
+    pub mode: ChanceMode,
+    /// Multiplies every numerator, like Oops! All 6s doubling all odds
+    pub odds_multiplier: u32,
+}
+
+impl Default for Chance {
+    fn default() -> Self {
+        Chance { mode: ChanceMode::WorstCase, odds_multiplier: 1 }
+    }
+}
+
+/// One chance roll made while scoring
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Roll {
+    pub what: String,
+    /// Chance of success after the odds multiplier, capped at 1
+    pub probability: f64,
+    pub hit: bool,
+    /// Whether the roll can change the score, unlike e.g. Lucky +$20 or Glass breaking
+    pub scoring: bool,
+}
+
+/// Deterministic across platforms, unlike the float rounding of fancier generators
+#[derive(Clone, Debug)]
+pub(crate) struct SplitMix64(u64);
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        SplitMix64(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        ((self.next_u64() >> 11) as f64) / ((1u64 << 53) as f64)
+    }
+}
+
+/// Per-`Score` state of the chance subsystem
+#[derive(Clone, Debug)]
+pub(crate) struct ChanceState {
+    pub(crate) chance: Chance,
+    pub(crate) rng: SplitMix64,
+    pub(crate) rolls: Vec<Roll>,
+}
+
+impl ChanceState {
+    pub(crate) fn new(chance: Chance) -> Self {
+        let seed = match chance.mode {
+            ChanceMode::Sample { seed } => seed,
+            _ => 0,
+        };
+        ChanceState { chance, rng: SplitMix64::new(seed), rolls: Vec::new() }
+    }
+
+    /// Roll a `numerator in denominator` chance
+    pub(crate) fn roll(&mut self, numerator: u32, denominator: u32, what: &str, scoring: bool) -> bool {
+        let numerator = numerator * self.chance.odds_multiplier;
+        let probability = (f64::from(numerator) / f64::from(denominator)).min(1.0);
+        let hit =
+            numerator >= denominator ||
+            (match &self.chance.mode {
+                ChanceMode::Sample { .. } => self.rng.next_f64() < probability,
+                ChanceMode::WorstCase => false,
+                ChanceMode::Replay(outcomes) =>
+                    probability > 0.0 && outcomes.get(self.rolls.len()).copied().unwrap_or(false),
+            });
+        self.rolls.push(Roll { what: what.to_string(), probability, hit, scoring });
+        hit
+    }
+}
+
+/// One way a round can play out
+pub struct Outcome {
+    /// Probability of exactly these rolls happening
+    pub probability: f64,
+    pub score: Score,
+}
+
+/// Most outcomes `Distribution::exact` enumerates before giving up
+pub const MAX_OUTCOMES: usize = 4096;
+
+/// Scores every combination of chance outcomes in the round
+///
+/// The probabilities of the returned outcomes sum to 1. Only rolls that can change
+/// the score are branched on; the rest, like Lucky +$20 or Glass breaking, fail in
+/// every outcome. The number of outcomes doubles with every uncertain scoring roll,
+/// so this gives up with `None` once there would be more than `limit` of them.
+pub fn outcomes(
+    round: &RoundInput,
+    arithmetic: Arithmetic,
+    odds_multiplier: u32,
+    limit: usize
+) -> Option<Vec<Outcome>> {
+    let mut outcomes = Vec::new();
+    let mut pending = vec![Vec::new()];
+
+    while let Some(forced) = pending.pop() {
+        let chance = Chance { mode: ChanceMode::Replay(forced.clone()), odds_multiplier };
+        let mut score = Score::with_arithmetic(round.clone(), arithmetic).with_chance(chance);
+        score.calculate_score();
+
+        // every uncertain roll past the forced prefix failed, so branch on it succeeding
+        let rolls = score.rolls();
+        for (i, roll) in rolls.iter().enumerate().skip(forced.len()) {
+            if roll.scoring && roll.probability > 0.0 && roll.probability < 1.0 {
+                let mut branch: Vec<bool> = rolls[..i]
+                    .iter()
+                    .map(|r| r.hit)
+                    .collect();
+                branch.push(true);
+                pending.push(branch);
+            }
+        }
+
+        let probability = rolls
+            .iter()
+            .filter(|r| r.scoring)
+            .map(|r| if r.hit { r.probability } else { 1.0 - r.probability })
+            .product();
+        outcomes.push(Outcome { probability, score });
+        // every pending branch is at least one more outcome
+        if outcomes.len() + pending.len() > limit {
+            return None;
+        }
+    }
+    Some(outcomes)
+}
+
+/// The exact expected final score over every chance outcome
+pub fn expected_total(outcomes: &[Outcome]) -> f64 {
+    outcomes
+        .iter()
+        .map(|o| o.probability * o.score.total().to_f64())
+        .sum()
+}
+
+impl Outcome {
+    pub fn total(&self) -> Number {
+        self.score.total()
+    }
+}
+
+/// The distribution of final scores over the chance outcomes of a round
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Distribution {
+    /// `(total, probability)` pairs sorted by total, each total listed once
+    pub totals: Vec<(f64, f64)>,
+}
+
+impl Distribution {
+    /// Enumerates every outcome, see `outcomes`; `None` past `MAX_OUTCOMES` of them
+    pub fn exact(round: &RoundInput, arithmetic: Arithmetic, odds_multiplier: u32) -> Option<Self> {
+        let outcomes = outcomes(round, arithmetic, odds_multiplier, MAX_OUTCOMES)?;
+        Some(Self::from_weighted(
+            outcomes
+                .iter()
+                .map(|o| (o.total().to_f64(), o.probability))
+                .collect()
+        ))
+    }
+
+    /// Scores `samples` runs seeded `seed`, `seed + 1`, ..., for rounds too big to enumerate
+    ///
+    /// `samples` must be at least 1.
+    pub fn sampled(
+        round: &RoundInput,
+        arithmetic: Arithmetic,
+        odds_multiplier: u32,
+        seed: u64,
+        samples: u32
+    ) -> Self {
+        let weight = 1.0 / f64::from(samples);
+        let totals = (0..u64::from(samples))
+            .map(|i| {
+                let mode = ChanceMode::Sample { seed: seed.wrapping_add(i) };
+                let mut score = Score::with_arithmetic(round.clone(), arithmetic).with_chance(
+                    Chance { mode, odds_multiplier }
+                );
+                score.calculate_score();
+                (score.total().to_f64(), weight)
+            })
+            .collect();
+        Self::from_weighted(totals)
+    }
+
+    fn from_weighted(mut weighted: Vec<(f64, f64)>) -> Self {
+        weighted.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut totals: Vec<(f64, f64)> = Vec::new();
+        for (total, probability) in weighted {
+            match totals.last_mut() {
+                Some(last) if last.0 == total => last.1 += probability,
+                _ => totals.push((total, probability)),
+            }
+        }
+        Distribution { totals }
+    }
+
+    pub fn min(&self) -> f64 {
+        self.totals.first().map_or(0.0, |t| t.0)
+    }
+
+    pub fn max(&self) -> f64 {
+        self.totals.last().map_or(0.0, |t| t.0)
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.totals
+            .iter()
+            .map(|(total, probability)| total * probability)
+            .sum()
+    }
+
+    /// The smallest total `t` with `P(total <= t) >= p`, `p` in `[0, 1]`
+    pub fn percentile(&self, p: f64) -> f64 {
+        let mut cumulative = 0.0;
+        for (total, probability) in &self.totals {
+            cumulative += probability;
+            // allow for rounding in the summed probabilities
+            if cumulative >= p - 1e-9 {
+                return *total;
+            }
+        }
+        self.max()
+    }
+
+    /// Probability of scoring at least `target`
+    pub fn probability_at_least(&self, target: f64) -> f64 {
+        self.totals
+            .iter()
+            .filter(|(total, _)| *total >= target)
+            .map(|(_, probability)| probability)
+            .sum::<f64>()
+            .min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse<T: std::str::FromStr<Err = String>>(items: &[&str]) -> Vec<T> {
+        items
+            .iter()
+            .map(|item| item.parse().unwrap())
+            .collect()
+    }
+
+    /// A Lucky and a Glass ten, with money-only jokers on the held King
+    fn lucky_glass() -> RoundInput {
+        RoundInput {
+            cards_played: parse(&["10♥ Lucky", "10♠ Glass"]),
+            cards_held_in_hand: parse(&["K♠"]),
+            jokers: parse(&["Reserved Parking", "Business Card"]),
+            ..RoundInput::default()
+        }
+    }
+
+    fn rolled(round: &RoundInput, mode: ChanceMode, odds_multiplier: u32) -> Score {
+        let chance = Chance { mode, odds_multiplier };
+        let mut score = Score::new(round.clone()).with_chance(chance);
+        score.calculate_score();
+        score
+    }
+
+    #[test]
+    fn same_seed_same_rolls() {
+        for seed in 0..16 {
+            let first = rolled(&lucky_glass(), ChanceMode::Sample { seed }, 1);
+            let second = rolled(&lucky_glass(), ChanceMode::Sample { seed }, 1);
+            assert_eq!(first.rolls(), second.rolls());
+            assert_eq!(first.total(), second.total());
+            assert_eq!(first.money(), second.money());
+        }
+    }
+
+    #[test]
+    fn different_seeds_diverge() {
+        let rolls: Vec<Vec<bool>> = (0..64)
+            .map(|seed| {
+                let score = rolled(&lucky_glass(), ChanceMode::Sample { seed }, 1);
+                score.rolls().iter().map(|roll| roll.hit).collect()
+            })
+            .collect();
+        assert!(rolls.iter().any(|hits| *hits != rolls[0]));
+        // over 64 seeds, the 1 in 5 Lucky +20 Mult both hits and misses
+        assert!(rolls.iter().any(|hits| hits[0]));
+        assert!(rolls.iter().any(|hits| !hits[0]));
+    }
+
+    #[test]
+    fn worst_case_misses_every_uncertain_roll() {
+        let score = rolled(&lucky_glass(), ChanceMode::WorstCase, 1);
+        let rolls: Vec<(&str, bool)> = score
+            .rolls()
+            .iter()
+            .map(|roll| (roll.what.as_str(), roll.hit))
+            .collect();
+        assert_eq!(rolls, [
+            ("Lucky +20 Mult", false),
+            ("Lucky +$20", false),
+            ("Glass breaks", false),
+            ("Reserved Parking", false),
+        ]);
+        // Pair (10 + 10 + 10, 2) x2 Glass, no money
+        assert_eq!(score.total().to_f64(), 120.0);
+        assert_eq!(score.money_delta(), 0);
+        assert!(score.destroyed_cards().is_empty());
+    }
+
+    #[test]
+    fn odds_multiplier_scales_and_caps() {
+        let probabilities = |odds_multiplier| -> Vec<f64> {
+            let score = rolled(&lucky_glass(), ChanceMode::WorstCase, odds_multiplier);
+            score.rolls().iter().map(|roll| roll.probability).collect()
+        };
+        assert_eq!(probabilities(1), [1.0 / 5.0, 1.0 / 15.0, 1.0 / 4.0, 1.0 / 2.0]);
+        assert_eq!(probabilities(2), [2.0 / 5.0, 2.0 / 15.0, 2.0 / 4.0, 1.0]);
+        assert_eq!(probabilities(20), [1.0, 1.0, 1.0, 1.0]);
+        // certain rolls hit even in the worst case
+        let score = rolled(&lucky_glass(), ChanceMode::WorstCase, 2);
+        let hits: Vec<bool> = score.rolls().iter().map(|roll| roll.hit).collect();
+        assert_eq!(hits, [false, false, false, true]);
+    }
+
+    #[test]
+    fn percentiles_and_targets() {
+        // 120 with probability 0.8, 1320 with 0.2
+        let distribution = Distribution::exact(&lucky_glass(), Arithmetic::Float, 1).unwrap();
+        assert_eq!(distribution.totals, [(120.0, 0.8), (1320.0, 0.2)]);
+        assert_eq!(distribution.percentile(0.0), 120.0);
+        assert_eq!(distribution.percentile(0.5), 120.0);
+        assert_eq!(distribution.percentile(0.8), 120.0);
+        assert_eq!(distribution.percentile(0.9), 1320.0);
+        assert_eq!(distribution.percentile(1.0), 1320.0);
+        assert_eq!(distribution.probability_at_least(0.0), 1.0);
+        assert_eq!(distribution.probability_at_least(120.0), 1.0);
+        assert_eq!(distribution.probability_at_least(121.0), 0.2);
+        assert_eq!(distribution.probability_at_least(1320.0), 0.2);
+        assert_eq!(distribution.probability_at_least(1321.0), 0.0);
+    }
+
+    #[test]
+    fn percentiles_allow_for_rounding() {
+        // ten tenths, listed backwards; the first eight sum to just under 0.8
+        let weighted = (1..=10).rev().map(|total| (f64::from(total), 0.1)).collect();
+        let distribution = Distribution::from_weighted(weighted);
+        assert_eq!(distribution.totals[0], (1.0, 0.1));
+        assert_eq!(distribution.percentile(0.8), 8.0);
+        assert_eq!(distribution.percentile(0.85), 9.0);
+        assert!((distribution.probability_at_least(9.0) - 0.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn sampled_totals_share_the_weight() {
+        let distribution = Distribution::sampled(&lucky_glass(), Arithmetic::Float, 1, 0, 100);
+        let weight: f64 = distribution.totals.iter().map(|t| t.1).sum();
+        assert!((weight - 1.0).abs() < 1e-9);
+        assert!(distribution.totals.iter().all(|t| t.0 == 120.0 || t.0 == 1320.0));
+    }
+
+    #[test]
+    fn only_scoring_rolls_branch() {
+        // Lucky +20 Mult branches; Lucky +$20, Glass breaking and Reserved Parking do not
+        let outcomes = outcomes(&lucky_glass(), Arithmetic::Float, 1, MAX_OUTCOMES).unwrap();
+        assert_eq!(outcomes.len(), 2);
+        let total: f64 = outcomes.iter().map(|o| o.probability).sum();
+        assert!((total - 1.0).abs() < 1e-12);
+        // Pair (10 + 10 + 10, 2) x2 Glass: 120, or 1320 with Lucky +20 Mult
+        assert_eq!(expected_total(&outcomes), 0.8 * 120.0 + 0.2 * 1320.0);
+    }
+
+    #[test]
+    fn enumeration_gives_up_past_the_limit() {
+        assert!(outcomes(&lucky_glass(), Arithmetic::Float, 1, 1).is_none());
+        assert!(Distribution::exact(&lucky_glass(), Arithmetic::Float, 1).is_some());
+    }
+
+    #[test]
+    fn guaranteed_rolls_do_not_branch() {
+        // Oops! All 6s times five makes Lucky +20 Mult certain
+        let outcomes = outcomes(&lucky_glass(), Arithmetic::Float, 5, MAX_OUTCOMES).unwrap();
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].total().to_f64(), 1320.0);
+    }
+}
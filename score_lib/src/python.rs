@@ -0,0 +1,188 @@
+//! Python bindings
+//!
+//! Rounds and results cross into Python as JSON, so everything but the thin
+//! `pyo3` layer in `bindings` is plain Rust and tested without an interpreter.
+
+use serde_json::{ json, Value };
+
+use crate::{ Arithmetic, RoundInput, Score };
+
+/// Read a round through `RoundInput`'s serde and validate it
+fn parse_round(json: &str) -> Result<RoundInput, String> {
+    let round: RoundInput = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    round.validate()?;
+    Ok(round)
+}
+
+fn calculate(round_json: &str, exact: bool) -> Result<Score, String> {
+    let arithmetic = if exact { Arithmetic::Exact } else { Arithmetic::Float };
+    let mut score = Score::with_arithmetic(parse_round(round_json)?, arithmetic);
+    score.calculate_score();
+    Ok(score)
+}
+
+/// The dict `score` returns
+fn summary(score: &Score) -> Value {
+    let scored_cards: Vec<String> = score
+        .scored_cards()
+        .iter()
+        .map(|id| id.to_string())
+        .collect();
+    json!({
+        "score": score.total().to_f64(),
+        "exact_score": score.total().to_string(),
+        "chips": score.chips.to_f64(),
+        "mult": score.mult.to_f64(),
+        "money_delta": score.money_delta(),
+        "poker_hand": score.best_poker_hand().to_string(),
+        "rejection": score.rejection().map(|rejection| rejection.to_string()),
+        "scored_cards": scored_cards,
+    })
+}
+
+/// The list `breakdown` returns, one dict per trace entry
+fn entries(score: &Score) -> Value {
+    score
+        .trace()
+        .iter()
+        .map(|entry| {
+            json!({
+                "message": entry.message,
+                "chips": entry.chips,
+                "mult": entry.mult,
+                "card": entry.card.map(|id| id.to_string()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(feature = "python")]
+mod bindings {
+    use pyo3::{ exceptions::PyValueError, prelude::*, types::{ PyDict, PyList } };
+    use serde_json::Value;
+
+    use crate::Score;
+
+    fn calculate(round: &Bound<'_, PyDict>, exact: bool) -> PyResult<Score> {
+        let json: String = round.py().import("json")?.call_method1("dumps", (round,))?.extract()?;
+        super::calculate(&json, exact).map_err(PyValueError::new_err)
+    }
+
+    fn to_python<'py>(py: Python<'py>, value: &Value) -> PyResult<Bound<'py, PyAny>> {
+        py.import("json")?.call_method1("loads", (value.to_string(),))
+    }
+
+    /// Score a round dict with the keys of a YAML round file (`cards_played`,
+    /// `cards_held_in_hand`, `jokers`, `blind`, ...); `exact` scores with exact rational
+    /// arithmetic
+    #[pyfunction]
+    #[pyo3(signature = (round, exact = false))]
+    fn score<'py>(
+        py: Python<'py>,
+        round: &Bound<'py, PyDict>,
+        exact: bool
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let summary = super::summary(&calculate(round, exact)?);
+        Ok(to_python(py, &summary)?.downcast_into()?)
+    }
+
+    /// The scoring breakdown of a round dict, one dict per trace entry
+    #[pyfunction]
+    #[pyo3(signature = (round, exact = false))]
+    fn breakdown<'py>(
+        py: Python<'py>,
+        round: &Bound<'py, PyDict>,
+        exact: bool
+    ) -> PyResult<Bound<'py, PyList>> {
+        let entries = super::entries(&calculate(round, exact)?);
+        Ok(to_python(py, &entries)?.downcast_into()?)
+    }
+
+    /// Score many round dicts in one call, in order
+    #[pyfunction]
+    #[pyo3(signature = (rounds, exact = false))]
+    fn score_many<'py>(
+        py: Python<'py>,
+        rounds: Vec<Bound<'py, PyDict>>,
+        exact: bool
+    ) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        rounds
+            .iter()
+            .map(|round| score(py, round, exact))
+            .collect()
+    }
+
+    #[pymodule]
+    fn score_lib(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(score, m)?)?;
+        m.add_function(wrap_pyfunction!(breakdown, m)?)?;
+        m.add_function(wrap_pyfunction!(score_many, m)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROUND: &str =
+        r#"{ "cards_played": ["K♥", "K♦"], "cards_held_in_hand": ["K♠"], "jokers": ["Baron"] }"#;
+
+    #[test]
+    fn summary_has_every_key() {
+        let score = calculate(ROUND, false).unwrap();
+        // Pair (10 + 10 + 10, 2), Baron x1.5 for the held King
+        assert_eq!(summary(&score), json!({
+            "score": 90.0,
+            "exact_score": "90",
+            "chips": 30.0,
+            "mult": 3.0,
+            "money_delta": 0,
+            "poker_hand": "Pair",
+            "rejection": null,
+            "scored_cards": ["played[0]", "played[1]"],
+        }));
+    }
+
+    #[test]
+    fn exact_picks_the_arithmetic() {
+        let exact = calculate(ROUND, true).unwrap();
+        assert_eq!(exact.total().arithmetic(), Arithmetic::Exact);
+        assert_eq!(summary(&exact)["exact_score"], "90");
+        assert_eq!(calculate(ROUND, false).unwrap().total().arithmetic(), Arithmetic::Float);
+    }
+
+    #[test]
+    fn rejections_are_reported_as_text() {
+        let round = r#"{ "cards_played": ["K♥", "K♦"], "blind": "The Psychic" }"#;
+        let summary = summary(&calculate(round, false).unwrap());
+        assert_eq!(summary["rejection"], "must play 5 cards, played 2");
+        assert_eq!(summary["score"], 0.0);
+    }
+
+    #[test]
+    fn entries_name_their_cards() {
+        let entries = entries(&calculate(ROUND, false).unwrap());
+        let entries = entries.as_array().unwrap();
+        let hands = json!({ "message": "[Pair, High Card]", "chips": 0.0, "mult": 0.0, "card": null });
+        assert_eq!(entries[0], hands);
+        let baron = entries
+            .iter()
+            .find(|entry| entry["message"].as_str().unwrap().starts_with("🃏 Baron"))
+            .unwrap();
+        assert_eq!(baron["card"], "held[0]");
+        assert_eq!(baron["mult"], 3.0);
+    }
+
+    #[test]
+    fn invalid_rounds_are_errors() {
+        assert!(calculate("{", false).is_err());
+        assert!(calculate(r#"{ "cards_played": ["K♥"], "blnd": "The Flint" }"#, false).is_err());
+        let round = r#"{
+            "cards_played": ["A♠"],
+            "jokers": ["Joker", "Joker", "Joker", "Joker", "Joker", "Joker"]
+        }"#;
+        let error = calculate(round, false).err();
+        assert_eq!(error.as_deref(), Some("6 jokers, but only 5 joker slots"));
+    }
+}
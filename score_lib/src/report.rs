@@ -0,0 +1,40 @@
+use ortalib::{ Chips, Mult };
+use serde::Serialize;
+
+use crate::{ CardId, IllegalPlay, Score, TraceEntry };
+
+/// Plain-data summary of a scored round, suitable for serializing to callers
+/// outside of Rust (HTTP, JS, ...)
+#[derive(Serialize, Debug, Clone)]
+pub struct ScoreReport {
+    /// Nearest `f64`, lossy past 2^53
+    pub score: f64,
+    /// `score` written out in full, every digit right under `Arithmetic::Exact`
+    pub exact_score: String,
+    pub chips: Chips,
+    pub mult: Mult,
+    pub money_delta: i64,
+    pub poker_hand: String,
+    pub scored_cards: Vec<CardId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejection: Option<IllegalPlay>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace: Option<Vec<TraceEntry>>,
+}
+
+impl Score {
+    /// Summarize the score, optionally including the trace
+    pub fn report(&self, with_trace: bool) -> ScoreReport {
+        ScoreReport {
+            score: self.total().to_f64(),
+            exact_score: self.total().to_string(),
+            chips: self.chips.to_f64(),
+            mult: self.mult.to_f64(),
+            money_delta: self.money_delta(),
+            poker_hand: self.best_poker_hand().to_string(),
+            scored_cards: self.scored_cards().to_vec(),
+            rejection: self.rejection(),
+            trace: with_trace.then(|| self.trace().to_vec()),
+        }
+    }
+}
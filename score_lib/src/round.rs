@@ -0,0 +1,188 @@
+use serde::{ Deserialize, Serialize };
+use std::collections::BTreeMap;
+
+use ortalib::PokerHand;
+
+use crate::{ card::{ Edition, PlayingCard }, poker_hand::hand_counts, Blind, JokerCard, JokerState, JokerUtils };
+
+/// Everything `Score` needs to know about a round
+///
+/// Deserializes from the same YAML / JSON as `ortalib::Round`, with cards read
+/// as `PlayingCard` so Stone, Gold and Lucky cards, Negative cards and seals are allowed.
+/// Unknown keys are an error, so a misspelled `blind` is not silently ignored.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RoundInput {
+    pub cards_played: Vec<PlayingCard>,
+
+    #[serde(default)]
+    pub cards_held_in_hand: Vec<PlayingCard>,
+
+    #[serde(default)]
+    pub jokers: Vec<JokerCard>,
+
+    /// Money at the start of the round, for jokers like Bull and Bootstraps
+    #[serde(default)]
+    pub money: i64,
+
+    /// State of the scaling jokers, keyed by their index in `jokers`
+    ///
+    /// Jokers without an entry start fresh, e.g. Ice Cream at +100 Chips.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub joker_state: BTreeMap<usize, JokerState>,
+
+    /// The boss blind, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blind: Option<Blind>,
+
+    /// Poker hands played before this one
+    #[serde(default)]
+    pub hand_history: HandHistory,
+
+    /// Where the hand sits in the round, for jokers like Banner and Acrobat
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<RoundContext>,
+
+    /// Every card in the deck, including the ones played and held
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_deck: Option<Vec<PlayingCard>>,
+
+    /// Joker slots before Negative jokers add theirs, `DEFAULT_JOKER_SLOTS` when not given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub joker_slots: Option<usize>,
+}
+
+/// Joker slots of a run without vouchers or Negative jokers
+pub const DEFAULT_JOKER_SLOTS: usize = 5;
+
+/// The rest of the round around the hand being scored
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundContext {
+    /// Hands left after this one, 0 on the final hand
+    pub hands_left: u32,
+    pub discards_left: u32,
+    /// Hand size before jokers like Juggler and Stuntman change it
+    pub hand_size: u32,
+    /// Cards left in the deck
+    pub deck_size: u32,
+}
+
+impl RoundInput {
+    /// Hand size after the jokers' changes and Negative cards, when the round has a context
+    pub fn hand_size(&self) -> Option<i64> {
+        let jokers: i64 = self.jokers
+            .iter()
+            .filter(|joker| !joker.debuffed)
+            .map(|joker| joker.hand_size_delta())
+            .sum();
+        let negative_cards = self.cards_played
+            .iter()
+            .chain(&self.cards_held_in_hand)
+            .filter(|card| card.edition == Some(Edition::Negative))
+            .count() as i64;
+        self.context.map(|context| i64::from(context.hand_size) + jokers + negative_cards)
+    }
+
+    /// Joker slots, +1 for every Negative joker
+    pub fn joker_slots(&self) -> usize {
+        let negative = self.jokers
+            .iter()
+            .filter(|joker| joker.edition == Some(Edition::Negative))
+            .count();
+        self.joker_slots.unwrap_or(DEFAULT_JOKER_SLOTS) + negative
+    }
+
+    /// Checks the round is one that can actually be played
+    ///
+    /// Play size and hand size are only checked for rounds with a context.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.context.is_some() && self.cards_played.len() > 5 {
+            return Err(format!("at most 5 cards can be played, got {}", self.cards_played.len()));
+        }
+        let (jokers, slots) = (self.jokers.len(), self.joker_slots());
+        if jokers > slots {
+            return Err(format!("{jokers} jokers, but only {slots} joker slots"));
+        }
+        if let Some(hand_size) = self.hand_size() {
+            let in_hand = self.cards_played.len() + self.cards_held_in_hand.len();
+            if (in_hand as i64) > hand_size {
+                return Err(format!("{in_hand} cards played and held, but the hand size is {hand_size}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How often each poker hand has been played, e.g. for Supernova and The Eye
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct HandHistory {
+    /// Plays per poker hand this run
+    #[serde(with = "hand_counts")]
+    pub run: BTreeMap<PokerHand, u32>,
+    /// Plays per poker hand against the current blind
+    #[serde(with = "hand_counts")]
+    pub round: BTreeMap<PokerHand, u32>,
+}
+
+impl HandHistory {
+    pub fn run_plays(&self, hand: PokerHand) -> u32 {
+        self.run.get(&hand).copied().unwrap_or(0)
+    }
+
+    pub fn round_plays(&self, hand: PokerHand) -> u32 {
+        self.round.get(&hand).copied().unwrap_or(0)
+    }
+
+    pub fn record(&mut self, hand: PokerHand) {
+        *self.run.entry(hand).or_insert(0) += 1;
+        *self.round.entry(hand).or_insert(0) += 1;
+    }
+}
+
+/// What carries over from one hand to the next, emitted after scoring so it can
+/// be pasted into the next round
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RunState {
+    pub money: i64,
+    pub joker_state: BTreeMap<usize, JokerState>,
+    pub hand_history: HandHistory,
+}
+
+impl From<ortalib::Round> for RoundInput {
+    fn from(round: ortalib::Round) -> Self {
+        RoundInput {
+            cards_played: round.cards_played.into_iter().map(PlayingCard::from).collect(),
+            cards_held_in_hand: round.cards_held_in_hand.into_iter().map(PlayingCard::from).collect(),
+            jokers: round.jokers.into_iter().map(JokerCard::from).collect(),
+            money: 0,
+            joker_state: BTreeMap::new(),
+            blind: None,
+            hand_history: HandHistory::default(),
+            context: None,
+            full_deck: None,
+            joker_slots: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round(played: usize, context: Option<RoundContext>) -> RoundInput {
+        RoundInput {
+            cards_played: vec!["2♠".parse().unwrap(); played],
+            context,
+            ..RoundInput::default()
+        }
+    }
+
+    #[test]
+    fn play_size_is_only_checked_with_a_context() {
+        let context = RoundContext { hands_left: 1, discards_left: 0, hand_size: 8, deck_size: 40 };
+        assert!(round(6, None).validate().is_ok());
+        assert!(round(5, Some(context)).validate().is_ok());
+        assert!(round(6, Some(context)).validate().is_err());
+    }
+}
@@ -0,0 +1,33 @@
+use ortalib::{ Chips, Mult };
+use serde::Serialize;
+
+use crate::CardId;
+
+/// One step of the scoring breakdown, with the chips and mult as they stood
+/// right after it
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct TraceEntry {
+    pub message: String,
+    pub chips: Chips,
+    pub mult: Mult,
+    /// The card being scored or held when the step happened, if any
+    pub card: Option<CardId>,
+}
+
+/// Records a formatted trace entry on the given `Score`
+///
+/// Nothing is printed; callers read the entries back through `Score::trace`.
+#[macro_export]
+macro_rules! explain {
+    ($s:expr, $($arg:tt)*) => {
+        {
+            let entry = $crate::TraceEntry {
+                message: format!($($arg)*),
+                chips: $s.chips.to_f64(),
+                mult: $s.mult.to_f64(),
+                card: $s.current_card,
+            };
+            $s.trace.push(entry);
+        }
+    };
+}
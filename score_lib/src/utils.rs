@@ -1,38 +1,67 @@
 use std::collections::HashMap;
-use crate::card::CardUtils;
+use crate::card::{ CardUtils, Enhancement, PlayingCard };
 
-use ortalib::{ Card, Rank, Suit };
+use ortalib::{ Rank, Suit };
 
-pub fn group_poker_by_rank(cards_played: &Vec<Card>) -> HashMap<Rank, Vec<Card>> {
+/// Groups the positions of `cards_played` by rank, leaving out rankless Stone cards
+pub fn group_poker_by_rank(cards_played: &[PlayingCard]) -> HashMap<Rank, Vec<usize>> {
     let mut rank_groups = HashMap::new();
-    for card in cards_played {
-        rank_groups.entry(card.rank).or_insert_with(Vec::new).push(*card);
+    for (i, card) in cards_played.iter().enumerate() {
+        if !card.is_stone() {
+            rank_groups.entry(card.rank).or_insert_with(Vec::new).push(i);
+        }
     }
-    return rank_groups;
+    rank_groups
 }
 
-pub fn count_poker_by_rank(cards_played: &Vec<Card>) -> HashMap<Rank, i32> {
-    let mut m: HashMap<Rank, i32> = HashMap::new();
-    for card in cards_played {
+/// Counts the cards of each rank, leaving out rankless Stone cards
+pub fn count_poker_by_rank(cards: &[PlayingCard]) -> HashMap<Rank, usize> {
+    let mut m: HashMap<Rank, usize> = HashMap::new();
+    for card in cards.iter().filter(|card| !card.is_stone()) {
         *m.entry(card.rank).or_insert(0) += 1;
     }
-    return m;
+    m
 }
 
-/// Finds the maximum number of cards with the same rank
-pub fn max_num_of_rank(cards_played: &Vec<Card>) -> i32 {
-    let m = count_poker_by_rank(cards_played);
-    if let Some((&_, &count)) = m.iter().max_by_key(|&(_, count)| count) {
-        return count;
-    } else {
-        return 0;
+/// Counts the cards with each enhancement
+pub fn count_poker_by_enhancement(cards: &[PlayingCard]) -> HashMap<Enhancement, usize> {
+    let mut m: HashMap<Enhancement, usize> = HashMap::new();
+    for enhancement in cards.iter().filter_map(|card| card.enhancement) {
+        *m.entry(enhancement).or_insert(0) += 1;
     }
+    m
 }
 
-/// Counts cards that match any of the specified suits (including wild cards)
-pub fn num_of_poker_by_suits(cards: &Vec<Card>, suits: Vec<Suit>) -> usize {
-    return cards
+/// Composition of the full deck, for jokers like Steel Joker and Erosion
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeckStats {
+    pub size: usize,
+    pub by_rank: HashMap<Rank, usize>,
+    pub by_enhancement: HashMap<Enhancement, usize>,
+}
+
+impl DeckStats {
+    pub fn new(deck: &[PlayingCard]) -> Self {
+        DeckStats {
+            size: deck.len(),
+            by_rank: count_poker_by_rank(deck),
+            by_enhancement: count_poker_by_enhancement(deck),
+        }
+    }
+
+    pub fn rank(&self, rank: Rank) -> usize {
+        self.by_rank.get(&rank).copied().unwrap_or(0)
+    }
+
+    pub fn enhancement(&self, enhancement: Enhancement) -> usize {
+        self.by_enhancement.get(&enhancement).copied().unwrap_or(0)
+    }
+}
+
+/// Counts cards that match any of the specified suits (including wild cards, but not Stone cards)
+pub fn num_of_poker_by_suits(cards: &[PlayingCard], suits: Vec<Suit>) -> usize {
+    cards
         .iter()
-        .filter(|card| (suits.contains(&card.suit) || card.is_wild()))
-        .count();
+        .filter(|card| !card.is_stone() && (suits.contains(&card.suit) || card.is_wild()))
+        .count()
 }
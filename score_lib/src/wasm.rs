@@ -0,0 +1,100 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{ Arithmetic, RoundInput, Score };
+
+fn score_json(round_json: &str, exact: Option<bool>, with_trace: bool) -> Result<String, JsValue> {
+    let round: RoundInput = serde_json
+        ::from_str(round_json)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    round.validate().map_err(|e| JsValue::from_str(&e))?;
+    let arithmetic = if exact == Some(true) { Arithmetic::Exact } else { Arithmetic::Float };
+    let mut score = Score::with_arithmetic(round, arithmetic);
+    score.calculate_score();
+
+    serde_json::to_string(&score.report(with_trace)).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Score a JSON `RoundInput`, returning a JSON `ScoreReport` without the trace
+///
+/// `exact` scores with `Arithmetic::Exact`, float when left out.
+#[wasm_bindgen]
+pub fn score(round_json: &str, exact: Option<bool>) -> Result<String, JsValue> {
+    score_json(round_json, exact, false)
+}
+
+/// Score a JSON `RoundInput`, returning a JSON `ScoreReport` including the trace
+#[wasm_bindgen]
+pub fn explain(round_json: &str, exact: Option<bool>) -> Result<String, JsValue> {
+    score_json(round_json, exact, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    const ROUND: &str = r#"{ "cards_played": ["Q♣", "Q♠"], "jokers": ["Gluttonous Joker"] }"#;
+
+    fn report(json: &str) -> serde_json::Value {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn keys(value: &serde_json::Value) -> Vec<&str> {
+        value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(|key| key.as_str())
+            .collect()
+    }
+
+    #[wasm_bindgen_test]
+    fn reports_have_the_score_report_shape() {
+        let scored = report(&score(ROUND, None).unwrap());
+        let fields = ["chips", "exact_score", "money_delta", "mult", "poker_hand", "score", "scored_cards"];
+        assert_eq!(keys(&scored), fields);
+
+        let explained = report(&explain(ROUND, None).unwrap());
+        assert_eq!(keys(&explained), [&fields[..], &["trace"]].concat());
+        assert_eq!(keys(&explained["trace"][0]), ["card", "chips", "message", "mult"]);
+
+        // a rejected play is still a report, with the reason as data
+        let rejected = r#"{ "cards_played": ["Q♣", "Q♠"], "blind": "The Psychic" }"#;
+        assert_eq!(report(&score(rejected, None).unwrap())["rejection"], serde_json::json!({
+            "reason": "too_few_cards",
+            "required": 5,
+            "played": 2,
+        }));
+    }
+
+    #[wasm_bindgen_test]
+    fn exact_defaults_to_float() {
+        // past 2^53, where floating point loses the last digit
+        let held = ["\"K♠ Steel\""; 18].join(", ");
+        let round = format!(
+            r#"{{ "cards_played": ["A♠ Glass Polychrome", "A♣ Glass Polychrome"],
+                 "cards_held_in_hand": [{held}], "jokers": ["Baron Polychrome", "Mime"] }}"#
+        );
+        let exact_score = |exact| report(&score(&round, exact).unwrap())["exact_score"].clone();
+        assert_eq!(exact_score(None), "4121776079310010");
+        assert_eq!(exact_score(Some(false)), "4121776079310010");
+        assert_eq!(exact_score(Some(true)), "4121776079310009");
+    }
+
+    #[wasm_bindgen_test]
+    fn errors_are_js_strings() {
+        let error = score("{", None).unwrap_err();
+        assert_eq!(error.as_string().unwrap(), "EOF while parsing an object at line 1 column 1");
+
+        let error = score(r#"{ "cards_played": ["Q♣"], "blnd": "The Flint" }"#, None).unwrap_err();
+        assert!(error.as_string().unwrap().starts_with("unknown field `blnd`"));
+
+        let context = r#"{ "hands_left": 1, "discards_left": 0, "hand_size": 2, "deck_size": 40 }"#;
+        let round = format!(
+            r#"{{ "cards_played": ["Q♣", "Q♠"], "cards_held_in_hand": ["2♥"], "context": {context} }}"#
+        );
+        let error = explain(&round, None).unwrap_err();
+        assert_eq!(error.as_string().unwrap(), "3 cards played and held, but the hand size is 2");
+    }
+}
@@ -1,45 +1,167 @@
-use score_lib::{ Score, config, explain };
+mod server;
+
+use score_lib::{
+    probability::{ Chance, ChanceMode, Distribution, MAX_OUTCOMES },
+    Arithmetic,
+    RoundInput,
+    Score,
+};
 
 use std::{ error::Error, fs::File, io::{ Read, stdin }, path::{ Path, PathBuf } };
 
 use clap::Parser;
-use ortalib::{ Chips, Mult, Round };
+
+/// Runs sampled by `--distribution` when a round has too many outcomes to enumerate
+const DEFAULT_SAMPLES: u32 = 10_000;
 
 #[derive(Parser)]
 struct Opts {
-    file: PathBuf,
+    #[arg(required_unless_present = "serve")]
+    file: Option<PathBuf>,
 
     #[arg(long)]
     explain: bool,
+
+    /// Use exact rational arithmetic instead of floating point
+    #[arg(long)]
+    exact: bool,
+
+    /// Roll chance-based effects with this seed instead of assuming none trigger
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Multiply the odds of every chance-based effect, like Oops! All 6s
+    #[arg(long, default_value_t = 1)]
+    odds_multiplier: u32,
+
+    /// Print the distribution of final scores over every chance outcome
+    #[arg(long)]
+    distribution: bool,
+
+    /// With `--distribution`, also print the probability of scoring at least this
+    #[arg(long, requires = "distribution")]
+    target: Option<f64>,
+
+    /// With `--distribution`, sample this many seeded runs instead of enumerating outcomes;
+    /// rounds with too many outcomes to enumerate are sampled anyway
+    #[arg(long, requires = "distribution", value_parser = clap::value_parser!(u32).range(1..))]
+    samples: Option<u32>,
+
+    /// After the score, print the money and joker state to carry into the next hand
+    #[arg(long)]
+    emit_state: bool,
+
+    /// Serve `POST /score` and `POST /explain` on this address instead of scoring a file
+    #[arg(long, value_name = "ADDR")]
+    serve: Option<String>,
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
     let opts = Opts::parse();
+    if let Some(addr) = &opts.serve {
+        return server::run(addr);
+    }
     let round = parse_round(&opts)?;
-    config::init(opts.explain);
 
-    let (chips, mult) = score(round);
+    let arithmetic = if opts.exact { Arithmetic::Exact } else { Arithmetic::Float };
+    if opts.distribution {
+        // blinds reject the play itself, whatever the rolls
+        let worst_case = score(round.clone(), arithmetic, Chance::default(), false);
+        if let Some(rejection) = worst_case.rejection() {
+            return Err(format!("illegal play: {rejection}").into());
+        }
+        let sampled = |samples| {
+            Distribution::sampled(&round, arithmetic, opts.odds_multiplier, opts.seed.unwrap_or(0), samples)
+        };
+        let distribution = match opts.samples {
+            Some(samples) => sampled(samples),
+            None =>
+                Distribution::exact(&round, arithmetic, opts.odds_multiplier).unwrap_or_else(|| {
+                    eprintln!("warning: over {MAX_OUTCOMES} outcomes, sampling {DEFAULT_SAMPLES} runs instead");
+                    sampled(DEFAULT_SAMPLES)
+                }),
+        };
+        print_distribution(&distribution, opts.target);
+        return Ok(());
+    }
+    let mode = match opts.seed {
+        Some(seed) => ChanceMode::Sample { seed },
+        None => ChanceMode::WorstCase,
+    };
+    let chance = Chance { mode, odds_multiplier: opts.odds_multiplier };
+    let score = score(round, arithmetic, chance, opts.explain);
+    if let Some(rejection) = score.rejection() {
+        return Err(format!("illegal play: {rejection}").into());
+    }
 
-    println!("{}", (chips * mult).floor());
+    println!("{}", score.total());
+    // only rounds with money effects print the money line
+    if score.money_delta() != 0 {
+        println!("money: {:+}", score.money_delta());
+    }
+    if opts.emit_state {
+        print!("{}", serde_yaml::to_string(&score.run_state())?);
+    }
     Ok(())
 }
 
-fn parse_round(opts: &Opts) -> Result<Round, Box<dyn Error>> {
+fn parse_round(opts: &Opts) -> Result<RoundInput, Box<dyn Error>> {
     let mut input = String::new();
-    if opts.file == Path::new("-") {
+    let file = opts.file.as_deref().unwrap_or(Path::new("-"));
+    if file == Path::new("-") {
         stdin().read_to_string(&mut input)?;
     } else {
-        File::open(&opts.file)?.read_to_string(&mut input)?;
+        File::open(file)?.read_to_string(&mut input)?;
     }
 
-    let round = serde_yaml::from_str(&input)?;
+    let round: RoundInput = serde_yaml::from_str(&input)?;
+    round.validate()?;
     Ok(round)
 }
 
-fn score(round: Round) -> (Chips, Mult) {
-    explain!("{:?}", round);
-    let mut score = Score::new(round);
+fn score(
+    round: RoundInput,
+    arithmetic: Arithmetic,
+    chance: Chance,
+    explain: bool
+) -> Score {
+    if explain {
+        println!("{:?}", round);
+    }
+    let mut score = Score::with_arithmetic(round, arithmetic).with_chance(chance);
     score.calculate_score();
+    if explain {
+        score
+            .trace()
+            .iter()
+            .for_each(|entry| println!("{}", entry.message));
+    }
+
+    score
+}
 
-    (score.chips, score.mult)
+fn print_distribution(distribution: &Distribution, target: Option<f64>) {
+    println!("min: {}", distribution.min());
+    println!("max: {}", distribution.max());
+    println!("mean: {}", distribution.mean());
+    for p in [10, 25, 50, 75, 90] {
+        println!("p{p}: {}", distribution.percentile(f64::from(p) / 100.0));
+    }
+    if let Some(target) = target {
+        println!("P(score >= {target}): {}", distribution.probability_at_least(target));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_must_be_positive() {
+        let parse = |samples| {
+            Opts::try_parse_from(["ortalab", "round.yml", "--distribution", "--samples", samples])
+        };
+        assert!(parse("0").is_err());
+        assert_eq!(parse("1").unwrap().samples, Some(1));
+    }
 }
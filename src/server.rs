@@ -0,0 +1,260 @@
+use score_lib::{ Arithmetic, RoundInput, Score };
+
+use std::{
+    error::Error,
+    io::{ self, BufRead, BufReader, Read, Take, Write },
+    net::{ TcpListener, TcpStream },
+    thread,
+    time::Duration,
+};
+
+use serde_json::{ Value, json };
+
+/// Largest request body accepted, larger ones get 413 without being read
+const MAX_BODY: u64 = 1024 * 1024;
+
+/// Largest request line plus headers accepted, larger ones get 431
+const MAX_HEAD: u64 = 8 * 1024;
+
+/// How long a client may go quiet before its connection is dropped
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Serves the scorer over HTTP until the process is killed.
+///
+/// `POST /score` and `POST /explain` both take a `RoundInput` as JSON or YAML in the
+/// request body; `/explain` additionally returns the scoring trace. `?exact` scores with
+/// exact rational arithmetic.
+pub fn run(addr: &str) -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind(addr)?;
+    eprintln!("listening on http://{}", listener.local_addr()?);
+    serve(listener)
+}
+
+/// Handles connections on an already bound listener, one thread per request
+fn serve(listener: TcpListener) -> Result<(), Box<dyn Error>> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        thread::spawn(move || {
+            if let Err(e) = handle(stream) {
+                eprintln!("request failed: {e}");
+            }
+        });
+    }
+    Ok(())
+}
+
+fn handle(mut stream: TcpStream) -> Result<(), Box<dyn Error>> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut head = (&mut reader).take(MAX_HEAD);
+    let too_large = json!({ "error": format!("request headers are over {MAX_HEAD} bytes") });
+
+    let Some(request_line) = head_line(&mut head)? else {
+        return respond(&mut stream, 431, &too_large);
+    };
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    // only Content-Length matters to us, the rest of the headers are skipped
+    let mut content_length: u64 = 0;
+    loop {
+        let Some(header) = head_line(&mut head)? else {
+            return respond(&mut stream, 431, &too_large);
+        };
+        if header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            let Ok(length) = value.trim().parse() else {
+                let error = json!({ "error": format!("invalid Content-Length {:?}", value.trim()) });
+                return respond(&mut stream, 400, &error);
+            };
+            content_length = length;
+        }
+    }
+    if content_length > MAX_BODY {
+        let error = json!({ "error": format!("request body is over {MAX_BODY} bytes") });
+        return respond(&mut stream, 413, &error);
+    }
+    let mut body = Vec::new();
+    reader.take(content_length).read_to_end(&mut body)?;
+    if (body.len() as u64) < content_length {
+        return respond(&mut stream, 400, &json!({ "error": "request body ended early" }));
+    }
+
+    let (status, response) = route(&method, &path, &body);
+    respond(&mut stream, status, &response)
+}
+
+/// Reads one line of the request head, `None` once the head runs over `MAX_HEAD`
+fn head_line<R: BufRead>(head: &mut Take<R>) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    head.read_line(&mut line)?;
+    if head.limit() == 0 && !line.ends_with('\n') {
+        return Ok(None);
+    }
+    Ok(Some(line))
+}
+
+fn respond(stream: &mut TcpStream, status: u16, response: &Value) -> Result<(), Box<dyn Error>> {
+    let response = response.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason(status),
+        response.len(),
+        response
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+fn route(method: &str, path: &str, body: &[u8]) -> (u16, Value) {
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+    let arithmetic = if query.split('&').any(|param| param == "exact" || param == "exact=true") {
+        Arithmetic::Exact
+    } else {
+        Arithmetic::Float
+    };
+    match (method, path) {
+        ("POST", "/score") => score_body(body, arithmetic, false),
+        ("POST", "/explain") => score_body(body, arithmetic, true),
+        (_, "/score" | "/explain") => (405, json!({ "error": "only POST is supported" })),
+        _ => (404, json!({ "error": format!("no route for {path}") })),
+    }
+}
+
+fn score_body(body: &[u8], arithmetic: Arithmetic, explain: bool) -> (u16, Value) {
+    let input = match std::str::from_utf8(body) {
+        Ok(input) => input,
+        Err(e) => {
+            return (400, json!({ "error": e.to_string() }));
+        }
+    };
+    // YAML is a superset of JSON, so one parser covers both request formats
+    let round: RoundInput = match serde_yaml::from_str(input) {
+        Ok(round) => round,
+        Err(e) => {
+            return (400, json!({ "error": e.to_string() }));
+        }
+    };
+
+    if let Err(e) = round.validate() {
+        return (422, json!({ "error": e }));
+    }
+    let mut score = Score::with_arithmetic(round, arithmetic);
+    score.calculate_score();
+    if let Some(rejection) = score.rejection() {
+        return (422, json!({ "error": rejection.to_string(), "rejection": rejection }));
+    }
+
+    (200, json!(score.report(explain)))
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        431 => "Request Header Fields Too Large",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Starts the server on a free local port and returns its address
+    fn start() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || serve(listener).map_err(|e| e.to_string()));
+        addr
+    }
+
+    /// Sends a raw request and returns the status code and JSON body
+    fn send(addr: &str, request: &str) -> (u16, Value) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let status = response.split_whitespace().nth(1).unwrap().parse().unwrap();
+        let body = response.split_once("\r\n\r\n").unwrap().1;
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    fn post(addr: &str, path: &str, body: &str) -> (u16, Value) {
+        let request = format!("POST {path} HTTP/1.1\r\nContent-Length: {}\r\n\r\n{body}", body.len());
+        send(addr, &request)
+    }
+
+    const ROUND: &str = r#"{ "cards_played": ["K♥", "K♦"], "cards_held_in_hand": [], "jokers": ["Joker"] }"#;
+
+    #[test]
+    fn score_returns_the_total() {
+        let addr = start();
+        let (status, body) = post(&addr, "/score", ROUND);
+        assert_eq!(status, 200);
+        // Pair: (10 + 10 + 10) x (2 + 4)
+        assert_eq!(body["score"], 180.0);
+        assert!(body.get("trace").is_none());
+    }
+
+    #[test]
+    fn exact_query_keeps_every_digit() {
+        let addr = start();
+        let (status, body) = post(&addr, "/score?exact", ROUND);
+        assert_eq!(status, 200);
+        assert_eq!(body["exact_score"], "180");
+    }
+
+    #[test]
+    fn explain_adds_the_trace() {
+        let addr = start();
+        let (status, body) = post(&addr, "/explain", ROUND);
+        assert_eq!(status, 200);
+        assert!(!body["trace"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn bad_requests_are_rejected() {
+        let addr = start();
+        assert_eq!(post(&addr, "/score", "cards_played: [").0, 400);
+        assert_eq!(post(&addr, "/nowhere", ROUND).0, 404);
+        assert_eq!(send(&addr, "GET /score HTTP/1.1\r\n\r\n").0, 405);
+    }
+
+    #[test]
+    fn oversized_bodies_are_refused_unread() {
+        let addr = start();
+        let request = "POST /score HTTP/1.1\r\nContent-Length: 999999999999\r\n\r\n";
+        let (status, _) = send(&addr, request);
+        assert_eq!(status, 413);
+    }
+
+    #[test]
+    fn oversized_headers_are_refused() {
+        let addr = start();
+        // one header line that never ends, exactly as long as the server reads
+        let request = "POST /score HTTP/1.1\r\nX-Padding: ";
+        let padding = "a".repeat(MAX_HEAD as usize - request.len());
+        let (status, _) = send(&addr, &format!("{request}{padding}"));
+        assert_eq!(status, 431);
+    }
+
+    #[test]
+    fn malformed_content_length_is_a_bad_request() {
+        let addr = start();
+        let (status, body) = send(&addr, "POST /score HTTP/1.1\r\nContent-Length: ten\r\n\r\n");
+        assert_eq!(status, 400);
+        assert_eq!(body["error"], "invalid Content-Length \"ten\"");
+    }
+}